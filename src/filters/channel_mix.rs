@@ -1,10 +1,11 @@
+use super::smoothing::SmoothedValue;
 use super::{AudioFilter, FilterError};
 
 pub struct ChannelMixFilter {
-    left_to_left: f64,
-    left_to_right: f64,
-    right_to_left: f64,
-    right_to_right: f64,
+    left_to_left: SmoothedValue,
+    left_to_right: SmoothedValue,
+    right_to_left: SmoothedValue,
+    right_to_right: SmoothedValue,
 }
 
 impl ChannelMixFilter {
@@ -14,6 +15,38 @@ impl ChannelMixFilter {
         right_to_left: f64,
         right_to_right: f64,
     ) -> Result<Self, FilterError> {
+        Self::validate(left_to_left, left_to_right, right_to_left, right_to_right)?;
+
+        Ok(Self {
+            left_to_left: SmoothedValue::new(left_to_left),
+            left_to_right: SmoothedValue::new(left_to_right),
+            right_to_left: SmoothedValue::new(right_to_left),
+            right_to_right: SmoothedValue::new(right_to_right),
+        })
+    }
+
+    /// Glides every mix factor to its new value
+    pub fn set_params(
+        &mut self,
+        left_to_left: f64,
+        left_to_right: f64,
+        right_to_left: f64,
+        right_to_right: f64,
+    ) -> Result<(), FilterError> {
+        Self::validate(left_to_left, left_to_right, right_to_left, right_to_right)?;
+        self.left_to_left.set(left_to_left);
+        self.left_to_right.set(left_to_right);
+        self.right_to_left.set(right_to_left);
+        self.right_to_right.set(right_to_right);
+        Ok(())
+    }
+
+    pub(super) fn validate(
+        left_to_left: f64,
+        left_to_right: f64,
+        right_to_left: f64,
+        right_to_right: f64,
+    ) -> Result<(), FilterError> {
         for (name, val) in [
             ("leftToLeft", left_to_left),
             ("leftToRight", left_to_right),
@@ -27,18 +60,12 @@ impl ChannelMixFilter {
                 )));
             }
         }
-
-        Ok(Self {
-            left_to_left,
-            left_to_right,
-            right_to_left,
-            right_to_right,
-        })
+        Ok(())
     }
 }
 
 impl AudioFilter for ChannelMixFilter {
-    fn process(&mut self, samples: &mut [i16], _sample_rate: u32) -> Result<(), FilterError> {
+    fn process(&mut self, samples: &mut [f32], sample_rate: u32) -> Result<(), FilterError> {
         if samples.len() % 2 != 0 {
             return Err(FilterError::BufferSizeMismatch);
         }
@@ -47,21 +74,34 @@ impl AudioFilter for ChannelMixFilter {
             let left = chunk[0] as f64;
             let right = chunk[1] as f64;
 
-            let new_left = left * self.left_to_left + right * self.right_to_left;
-            let new_right = left * self.left_to_right + right * self.right_to_right;
+            let left_to_left = self.left_to_left.next(sample_rate);
+            let left_to_right = self.left_to_right.next(sample_rate);
+            let right_to_left = self.right_to_left.next(sample_rate);
+            let right_to_right = self.right_to_right.next(sample_rate);
+
+            let new_left = left * left_to_left + right * right_to_left;
+            let new_right = left * left_to_right + right * right_to_right;
 
-            chunk[0] = new_left.clamp(i16::MIN as f64, i16::MAX as f64) as i16;
-            chunk[1] = new_right.clamp(i16::MIN as f64, i16::MAX as f64) as i16;
+            chunk[0] = new_left as f32;
+            chunk[1] = new_right as f32;
         }
 
         Ok(())
     }
 
     fn is_active(&self) -> bool {
-        (self.left_to_left - 1.0).abs() > f64::EPSILON
-            || self.left_to_right.abs() > f64::EPSILON
-            || self.right_to_left.abs() > f64::EPSILON
-            || (self.right_to_right - 1.0).abs() > f64::EPSILON
+        [
+            &self.left_to_left,
+            &self.left_to_right,
+            &self.right_to_left,
+            &self.right_to_right,
+        ]
+        .iter()
+        .any(|value| value.is_ramping())
+            || (self.left_to_left.current() - 1.0).abs() > f64::EPSILON
+            || self.left_to_right.current().abs() > f64::EPSILON
+            || self.right_to_left.current().abs() > f64::EPSILON
+            || (self.right_to_right.current() - 1.0).abs() > f64::EPSILON
     }
 
     fn name(&self) -> &'static str {
@@ -0,0 +1,145 @@
+use super::{AudioFilter, FilterError};
+use std::f64::consts::PI;
+
+pub struct ChorusFilter {
+    rate: f64,
+    depth: f64,
+    delay: f64,
+    mix: f64,
+    feedback: f64,
+    phase: f64,
+    buffer_left: Vec<f32>,
+    buffer_right: Vec<f32>,
+    write_pos: usize,
+}
+
+impl ChorusFilter {
+    /// Enough room for the longest base delay plus the deepest sweep at 96kHz
+    const MAX_DELAY: usize = 8192;
+
+    /// `rate` is in Hz, `delay` is the base delay in ms that the lfo sweeps around
+    pub fn new(
+        rate: f64,
+        depth: f64,
+        delay: f64,
+        mix: f64,
+        feedback: f64,
+    ) -> Result<Self, FilterError> {
+        if rate <= 0.0 || rate > 20.0 {
+            return Err(FilterError::InvalidParameter(format!(
+                "Chorus rate must be > 0 and ≤ 20, got {}",
+                rate
+            )));
+        }
+        if !(0.0..=1.0).contains(&depth) {
+            return Err(FilterError::InvalidParameter(format!(
+                "Chorus depth must be 0.0–1.0, got {}",
+                depth
+            )));
+        }
+        if !(1.0..=40.0).contains(&delay) {
+            return Err(FilterError::InvalidParameter(format!(
+                "Chorus delay must be 1–40ms, got {}",
+                delay
+            )));
+        }
+        if !(0.0..=1.0).contains(&mix) {
+            return Err(FilterError::InvalidParameter(format!(
+                "Chorus mix must be 0.0–1.0, got {}",
+                mix
+            )));
+        }
+        if !(0.0..0.95).contains(&feedback) {
+            return Err(FilterError::InvalidParameter(format!(
+                "Chorus feedback must be 0.0–0.95 (exclusive), got {}",
+                feedback
+            )));
+        }
+
+        Ok(Self {
+            rate,
+            depth,
+            delay,
+            mix,
+            feedback,
+            phase: 0.0,
+            buffer_left: vec![0.0; Self::MAX_DELAY],
+            buffer_right: vec![0.0; Self::MAX_DELAY],
+            write_pos: 0,
+        })
+    }
+
+    fn read(buffer: &[f32], position: f64) -> f32 {
+        let idx0 = position.floor() as usize % buffer.len();
+        let idx1 = (idx0 + 1) % buffer.len();
+        let frac = position.fract() as f32;
+        buffer[idx0] * (1.0 - frac) + buffer[idx1] * frac
+    }
+}
+
+impl AudioFilter for ChorusFilter {
+    fn process(&mut self, samples: &mut [f32], sample_rate: u32) -> Result<(), FilterError> {
+        if samples.len() % 2 != 0 {
+            return Err(FilterError::BufferSizeMismatch);
+        }
+
+        let phase_inc = 2.0 * PI * self.rate / sample_rate as f64;
+        let base = self.delay / 1000.0 * sample_rate as f64;
+        // the sweep never goes below 1 sample so the read position stays behind the writer
+        let sweep = (base - 1.0) * self.depth;
+        let max_delay = (Self::MAX_DELAY - 2) as f64;
+        let mix = self.mix as f32;
+        let feedback = self.feedback as f32;
+
+        for chunk in samples.chunks_exact_mut(2) {
+            let left = chunk[0];
+            let right = chunk[1];
+
+            // the right channel is swept in opposite phase for a wider stereo image
+            let delay_left = (base + sweep * self.phase.sin()).clamp(1.0, max_delay);
+            let delay_right = (base - sweep * self.phase.sin()).clamp(1.0, max_delay);
+
+            let len = Self::MAX_DELAY as f64;
+            let wet_left = Self::read(
+                &self.buffer_left,
+                (self.write_pos as f64 - delay_left + len) % len,
+            );
+            let wet_right = Self::read(
+                &self.buffer_right,
+                (self.write_pos as f64 - delay_right + len) % len,
+            );
+
+            self.buffer_left[self.write_pos] = left + wet_left * feedback;
+            self.buffer_right[self.write_pos] = right + wet_right * feedback;
+
+            let out_left = left * (1.0 - mix) + wet_left * mix;
+            let out_right = right * (1.0 - mix) + wet_right * mix;
+
+            chunk[0] = out_left;
+            chunk[1] = out_right;
+
+            self.write_pos = (self.write_pos + 1) % Self::MAX_DELAY;
+            self.phase += phase_inc;
+            if self.phase > 2.0 * PI {
+                self.phase -= 2.0 * PI;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn is_active(&self) -> bool {
+        self.mix > f64::EPSILON
+    }
+
+    fn name(&self) -> &'static str {
+        "Chorus"
+    }
+
+    fn reset(&mut self) {
+        self.phase = 0.0;
+        self.write_pos = 0;
+        self.buffer_left.fill(0.0);
+        self.buffer_right.fill(0.0);
+    }
+}
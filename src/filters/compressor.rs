@@ -0,0 +1,112 @@
+use super::{AudioFilter, FilterError};
+
+pub struct CompressorFilter {
+    threshold: f64,
+    ratio: f64,
+    attack: f64,
+    release: f64,
+    makeup_gain: f64,
+    envelope: f32,
+}
+
+impl CompressorFilter {
+    /// `threshold` and `makeup_gain` are in dB, `attack` and `release` are in ms
+    pub fn new(
+        threshold: f64,
+        ratio: f64,
+        attack: f64,
+        release: f64,
+        makeup_gain: f64,
+    ) -> Result<Self, FilterError> {
+        if !(-60.0..=0.0).contains(&threshold) {
+            return Err(FilterError::InvalidParameter(format!(
+                "Compressor threshold must be -60–0 dB, got {}",
+                threshold
+            )));
+        }
+        if !(1.0..=20.0).contains(&ratio) {
+            return Err(FilterError::InvalidParameter(format!(
+                "Compressor ratio must be 1–20, got {}",
+                ratio
+            )));
+        }
+        if attack <= 0.0 || release <= 0.0 {
+            return Err(FilterError::InvalidParameter(format!(
+                "Compressor attack and release must be > 0, got {} and {}",
+                attack, release
+            )));
+        }
+        if !(-24.0..=24.0).contains(&makeup_gain) {
+            return Err(FilterError::InvalidParameter(format!(
+                "Compressor gain must be -24–24 dB, got {}",
+                makeup_gain
+            )));
+        }
+
+        Ok(Self {
+            threshold,
+            ratio,
+            attack,
+            release,
+            makeup_gain,
+            envelope: 0.0,
+        })
+    }
+
+    fn coefficient(time_ms: f64, sample_rate: u32) -> f32 {
+        (-1.0 / (time_ms / 1000.0 * sample_rate as f64)).exp() as f32
+    }
+}
+
+impl AudioFilter for CompressorFilter {
+    fn process(&mut self, samples: &mut [f32], sample_rate: u32) -> Result<(), FilterError> {
+        if samples.len() % 2 != 0 {
+            return Err(FilterError::BufferSizeMismatch);
+        }
+
+        let attack = Self::coefficient(self.attack, sample_rate);
+        let release = Self::coefficient(self.release, sample_rate);
+        let threshold = self.threshold as f32;
+        let slope = 1.0 - 1.0 / self.ratio as f32;
+        let makeup = self.makeup_gain as f32;
+
+        for chunk in samples.chunks_exact_mut(2) {
+            let left = chunk[0];
+            let right = chunk[1];
+
+            // both channels share one detector so the stereo image does not shift
+            let peak = left.abs().max(right.abs());
+            let coeff = if peak > self.envelope {
+                attack
+            } else {
+                release
+            };
+            self.envelope = coeff * self.envelope + (1.0 - coeff) * peak;
+
+            let level = 20.0 * self.envelope.max(1e-6).log10();
+            let reduction = if level > threshold {
+                (level - threshold) * slope
+            } else {
+                0.0
+            };
+            let gain = 10_f32.powf((makeup - reduction) / 20.0);
+
+            chunk[0] = left * gain;
+            chunk[1] = right * gain;
+        }
+
+        Ok(())
+    }
+
+    fn is_active(&self) -> bool {
+        (self.ratio - 1.0).abs() > f64::EPSILON || self.makeup_gain.abs() > f64::EPSILON
+    }
+
+    fn name(&self) -> &'static str {
+        "Compressor"
+    }
+
+    fn reset(&mut self) {
+        self.envelope = 0.0;
+    }
+}
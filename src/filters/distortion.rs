@@ -46,11 +46,11 @@ impl DistortionFilter {
 }
 
 impl AudioFilter for DistortionFilter {
-    fn process(&mut self, samples: &mut [i16], _sample_rate: u32) -> Result<(), FilterError> {
+    fn process(&mut self, samples: &mut [f32], _sample_rate: u32) -> Result<(), FilterError> {
         for sample in samples.iter_mut() {
-            let input = *sample as f64 / i16::MAX as f64;
+            let input = *sample as f64;
             let distorted = self.distort(input);
-            *sample = (distorted * i16::MAX as f64).clamp(i16::MIN as f64, i16::MAX as f64) as i16;
+            *sample = distorted as f32;
         }
         Ok(())
     }
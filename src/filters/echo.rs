@@ -0,0 +1,115 @@
+use super::{AudioFilter, FilterError};
+
+pub struct EchoFilter {
+    delay: f64,
+    decay: f64,
+    mix: f64,
+    sample_rate: u32,
+    buffer_left: Vec<f32>,
+    buffer_right: Vec<f32>,
+    position: usize,
+}
+
+impl EchoFilter {
+    const MAX_DELAY: f64 = 5.0;
+
+    /// `delay` is in seconds, `decay` is how much of the echo is fed back into the next one
+    pub fn new(delay: f64, decay: f64, mix: f64, sample_rate: u32) -> Result<Self, FilterError> {
+        if delay <= 0.0 || delay > Self::MAX_DELAY {
+            return Err(FilterError::InvalidParameter(format!(
+                "Echo delay must be > 0 and ≤ {}s, got {}",
+                Self::MAX_DELAY,
+                delay
+            )));
+        }
+        if !(0.0..1.0).contains(&decay) {
+            return Err(FilterError::InvalidParameter(format!(
+                "Echo decay must be 0.0–1.0 (exclusive), got {}",
+                decay
+            )));
+        }
+        if !(0.0..=1.0).contains(&mix) {
+            return Err(FilterError::InvalidParameter(format!(
+                "Echo mix must be 0.0–1.0, got {}",
+                mix
+            )));
+        }
+
+        let length = Self::length(delay, sample_rate);
+
+        Ok(Self {
+            delay,
+            decay,
+            mix,
+            sample_rate,
+            buffer_left: vec![0.0; length],
+            buffer_right: vec![0.0; length],
+            position: 0,
+        })
+    }
+
+    /// Resizes the delay line for a new sample rate, the echoes in flight are dropped
+    pub fn set_sample_rate(&mut self, sample_rate: u32) {
+        if self.sample_rate == sample_rate {
+            return;
+        }
+
+        let length = Self::length(self.delay, sample_rate);
+        self.sample_rate = sample_rate;
+        self.buffer_left = vec![0.0; length];
+        self.buffer_right = vec![0.0; length];
+        self.position = 0;
+    }
+
+    fn length(delay: f64, sample_rate: u32) -> usize {
+        ((delay * sample_rate as f64) as usize).max(1)
+    }
+}
+
+impl AudioFilter for EchoFilter {
+    fn process(&mut self, samples: &mut [f32], sample_rate: u32) -> Result<(), FilterError> {
+        if samples.len() % 2 != 0 {
+            return Err(FilterError::BufferSizeMismatch);
+        }
+
+        self.set_sample_rate(sample_rate);
+
+        let decay = self.decay as f32;
+        let mix = self.mix as f32;
+
+        for chunk in samples.chunks_exact_mut(2) {
+            let left = chunk[0];
+            let right = chunk[1];
+
+            let echo_left = self.buffer_left[self.position];
+            let echo_right = self.buffer_right[self.position];
+
+            self.buffer_left[self.position] = left + echo_left * decay;
+            self.buffer_right[self.position] = right + echo_right * decay;
+
+            let out_left = left + echo_left * mix;
+            let out_right = right + echo_right * mix;
+
+            chunk[0] = out_left;
+            chunk[1] = out_right;
+
+            self.position = (self.position + 1) % self.buffer_left.len();
+        }
+
+        Ok(())
+    }
+
+    fn is_active(&self) -> bool {
+        self.mix > f64::EPSILON
+    }
+
+    fn name(&self) -> &'static str {
+        "Echo"
+    }
+
+    fn reset(&mut self) {
+        self.buffer_left.fill(0.0);
+        self.buffer_right.fill(0.0);
+        self.position = 0;
+    }
+}
@@ -1,10 +1,12 @@
+use super::smoothing::SmoothedValue;
 use super::{AudioFilter, FilterError};
 use crate::models::Equalizer;
 use std::f64::consts::PI;
 
 pub struct EqualizerFilter {
     bands: [BiquadFilter; 15],
-    gains: [f64; 15],
+    gains: [SmoothedValue; 15],
+    sample_rate: u32,
 }
 
 impl EqualizerFilter {
@@ -15,19 +17,37 @@ impl EqualizerFilter {
 
     const Q: f64 = 1.0;
 
+    /// Frames between coefficient updates while a gain is gliding
+    const CONTROL_FRAMES: usize = 32;
+
     pub fn new(sample_rate: u32) -> Self {
         let bands = Self::FREQUENCIES
             .map(|freq| BiquadFilter::peaking_eq(sample_rate as f64, freq, Self::Q, 0.0));
 
         Self {
             bands,
-            gains: [0.0; 15],
+            gains: [SmoothedValue::new(0.0); 15],
+            sample_rate,
         }
     }
 
-    pub fn from_bands(band_configs: &[Equalizer], sample_rate: u32) -> Result<Self, FilterError> {
-        let mut filter = Self::new(sample_rate);
+    /// Glides every band to the gains in `band_configs`, bands left out go back to flat
+    pub fn set_bands(&mut self, band_configs: &[Equalizer]) -> Result<(), FilterError> {
+        Self::validate(band_configs)?;
+
+        let mut gains = [0.0; 15];
+        for config in band_configs {
+            gains[config.band as usize] = config.gain;
+        }
+
+        for (smoothed, gain) in self.gains.iter_mut().zip(gains) {
+            smoothed.set(gain);
+        }
 
+        Ok(())
+    }
+
+    pub(super) fn validate(band_configs: &[Equalizer]) -> Result<(), FilterError> {
         for config in band_configs {
             if config.band > 14 {
                 return Err(FilterError::InvalidParameter(format!(
@@ -42,19 +62,26 @@ impl EqualizerFilter {
                     config.gain
                 )));
             }
+        }
+        Ok(())
+    }
 
-            let idx = config.band as usize;
-            filter.gains[idx] = config.gain;
-            filter.update_band(idx, config.gain, sample_rate);
+    /// Recomputes every band for a new sample rate, keeping the filter history and gains
+    pub fn set_sample_rate(&mut self, sample_rate: u32) {
+        if self.sample_rate == sample_rate {
+            return;
         }
 
-        Ok(filter)
+        self.sample_rate = sample_rate;
+        for index in 0..self.gains.len() {
+            self.update_band(index, self.gains[index].current());
+        }
     }
 
-    fn update_band(&mut self, band_index: usize, gain: f64, sample_rate: u32) {
+    fn update_band(&mut self, band_index: usize, gain: f64) {
         let db = gain * 6.0;
-        self.bands[band_index] = BiquadFilter::peaking_eq(
-            sample_rate as f64,
+        self.bands[band_index].set_peaking_eq(
+            self.sample_rate as f64,
             Self::FREQUENCIES[band_index],
             Self::Q,
             db,
@@ -63,31 +90,44 @@ impl EqualizerFilter {
 }
 
 impl AudioFilter for EqualizerFilter {
-    fn process(&mut self, samples: &mut [i16], _sample_rate: u32) -> Result<(), FilterError> {
+    fn process(&mut self, samples: &mut [f32], sample_rate: u32) -> Result<(), FilterError> {
         if samples.len() % 2 != 0 {
             return Err(FilterError::BufferSizeMismatch);
         }
 
-        for chunk in samples.chunks_exact_mut(2) {
-            let mut left = chunk[0] as f32;
-            let mut right = chunk[1] as f32;
+        self.set_sample_rate(sample_rate);
 
-            for (i, band) in self.bands.iter_mut().enumerate() {
-                if self.gains[i].abs() > f64::EPSILON {
-                    left = band.process_left(left);
-                    right = band.process_right(right);
+        for block in samples.chunks_mut(Self::CONTROL_FRAMES * 2) {
+            for index in 0..self.gains.len() {
+                if self.gains[index].is_ramping() {
+                    let gain = self.gains[index].advance((block.len() / 2) as u32, sample_rate);
+                    self.update_band(index, gain);
                 }
             }
 
-            chunk[0] = left.clamp(i16::MIN as f32, i16::MAX as f32) as i16;
-            chunk[1] = right.clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+            for chunk in block.chunks_exact_mut(2) {
+                let mut left = chunk[0];
+                let mut right = chunk[1];
+
+                for (i, band) in self.bands.iter_mut().enumerate() {
+                    if self.gains[i].current().abs() > f64::EPSILON {
+                        left = band.process_left(left);
+                        right = band.process_right(right);
+                    }
+                }
+
+                chunk[0] = left;
+                chunk[1] = right;
+            }
         }
 
         Ok(())
     }
 
     fn is_active(&self) -> bool {
-        self.gains.iter().any(|&g| g.abs() > f64::EPSILON)
+        self.gains
+            .iter()
+            .any(|gain| gain.is_ramping() || gain.current().abs() > f64::EPSILON)
     }
 
     fn name(&self) -> &'static str {
@@ -101,7 +141,7 @@ impl AudioFilter for EqualizerFilter {
     }
 }
 
-struct BiquadFilter {
+pub(super) struct BiquadFilter {
     b0: f32,
     b1: f32,
     b2: f32,
@@ -121,6 +161,31 @@ struct BiquadFilter {
 
 impl BiquadFilter {
     fn peaking_eq(sample_rate: f64, frequency: f64, q: f64, gain_db: f64) -> Self {
+        let mut filter = Self::empty();
+        filter.set_peaking_eq(sample_rate, frequency, q, gain_db);
+        filter
+    }
+
+    fn empty() -> Self {
+        Self {
+            b0: 1.0,
+            b1: 0.0,
+            b2: 0.0,
+            a1: 0.0,
+            a2: 0.0,
+            lx1: 0.0,
+            lx2: 0.0,
+            ly1: 0.0,
+            ly2: 0.0,
+            rx1: 0.0,
+            rx2: 0.0,
+            ry1: 0.0,
+            ry2: 0.0,
+        }
+    }
+
+    /// Replaces the coefficients while keeping the filter history, so retuning does not click
+    fn set_peaking_eq(&mut self, sample_rate: f64, frequency: f64, q: f64, gain_db: f64) {
         let a = 10_f64.powf(gain_db / 40.0);
         let omega = 2.0 * PI * frequency / sample_rate;
         let sin_omega = omega.sin();
@@ -134,6 +199,25 @@ impl BiquadFilter {
         let a1 = -2.0 * cos_omega;
         let a2 = 1.0 - alpha / a;
 
+        self.b0 = (b0 / a0) as f32;
+        self.b1 = (b1 / a0) as f32;
+        self.b2 = (b2 / a0) as f32;
+        self.a1 = (a1 / a0) as f32;
+        self.a2 = (a2 / a0) as f32;
+    }
+
+    pub(super) fn high_pass(sample_rate: f64, frequency: f64, q: f64) -> Self {
+        let omega = 2.0 * PI * frequency / sample_rate;
+        let cos_omega = omega.cos();
+        let alpha = omega.sin() / (2.0 * q);
+
+        let b0 = (1.0 + cos_omega) / 2.0;
+        let b1 = -(1.0 + cos_omega);
+        let b2 = (1.0 + cos_omega) / 2.0;
+        let a0 = 1.0 + alpha;
+        let a1 = -2.0 * cos_omega;
+        let a2 = 1.0 - alpha;
+
         Self {
             b0: (b0 / a0) as f32,
             b1: (b1 / a0) as f32,
@@ -151,7 +235,7 @@ impl BiquadFilter {
         }
     }
 
-    fn process_left(&mut self, input: f32) -> f32 {
+    pub(super) fn process_left(&mut self, input: f32) -> f32 {
         let output = self.b0 * input + self.b1 * self.lx1 + self.b2 * self.lx2
             - self.a1 * self.ly1
             - self.a2 * self.ly2;
@@ -164,7 +248,7 @@ impl BiquadFilter {
         output
     }
 
-    fn process_right(&mut self, input: f32) -> f32 {
+    pub(super) fn process_right(&mut self, input: f32) -> f32 {
         let output = self.b0 * input + self.b1 * self.rx1 + self.b2 * self.rx2
             - self.a1 * self.ry1
             - self.a2 * self.ry2;
@@ -177,7 +261,7 @@ impl BiquadFilter {
         output
     }
 
-    fn reset(&mut self) {
+    pub(super) fn reset(&mut self) {
         self.lx1 = 0.0;
         self.lx2 = 0.0;
         self.ly1 = 0.0;
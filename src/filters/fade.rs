@@ -0,0 +1,201 @@
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FadeCurve {
+    Linear,
+    Exponential,
+    Logarithmic,
+}
+
+impl From<&str> for FadeCurve {
+    fn from(value: &str) -> Self {
+        match value.to_lowercase().as_str() {
+            "exponential" | "exp" => FadeCurve::Exponential,
+            "logarithmic" | "log" => FadeCurve::Logarithmic,
+            _ => FadeCurve::Linear,
+        }
+    }
+}
+
+impl FadeCurve {
+    /// Maps the progress of a fade (0.0 to 1.0) to how far the gain has moved towards its target
+    #[inline]
+    pub fn shape(&self, progress: f32) -> f32 {
+        let progress = progress.clamp(0.0, 1.0);
+        match self {
+            FadeCurve::Linear => progress,
+            FadeCurve::Exponential => (10_f32.powf(progress) - 1.0) / 9.0,
+            FadeCurve::Logarithmic => (1.0 + 9.0 * progress).log10(),
+        }
+    }
+}
+
+/// Gain that moves towards a target over a fixed amount of frames
+pub struct GainRamp {
+    gain: f32,
+    from: f32,
+    target: f32,
+    length: u64,
+    elapsed: u64,
+    curve: FadeCurve,
+}
+
+impl Default for GainRamp {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl GainRamp {
+    pub fn new(gain: f32) -> Self {
+        Self {
+            gain,
+            from: gain,
+            target: gain,
+            length: 0,
+            elapsed: 0,
+            curve: FadeCurve::Linear,
+        }
+    }
+
+    pub fn set(&mut self, gain: f32) {
+        *self = Self::new(gain);
+    }
+
+    pub fn ramp_to(&mut self, target: f32, frames: u64, curve: FadeCurve) {
+        if frames == 0 {
+            return self.set(target);
+        }
+        self.from = self.gain;
+        self.target = target;
+        self.length = frames;
+        self.elapsed = 0;
+        self.curve = curve;
+    }
+
+    pub fn is_ramping(&self) -> bool {
+        self.elapsed < self.length
+    }
+
+    pub fn is_unity(&self) -> bool {
+        !self.is_ramping() && (self.gain - 1.0).abs() <= f32::EPSILON
+    }
+
+    /// Highest gain the ramp is at or heading to
+    pub fn peak(&self) -> f32 {
+        self.gain.max(self.target)
+    }
+
+    /// Advances the ramp by one frame and returns the gain for that frame
+    #[inline]
+    pub fn next(&mut self) -> f32 {
+        if self.elapsed < self.length {
+            self.elapsed += 1;
+            let progress = self.elapsed as f32 / self.length as f32;
+            self.gain = self.from + (self.target - self.from) * self.curve.shape(progress);
+        }
+        self.gain
+    }
+
+    /// Scales interleaved stereo samples, advancing the ramp once per frame
+    pub fn apply(&mut self, samples: &mut [f32]) {
+        if self.is_unity() {
+            return;
+        }
+
+        for chunk in samples.chunks_exact_mut(2) {
+            let gain = self.next();
+            chunk[0] *= gain;
+            chunk[1] *= gain;
+        }
+    }
+}
+
+struct TrackEndFade {
+    end_ms: u64,
+    duration_ms: u64,
+    curve: FadeCurve,
+}
+
+/// Fade envelopes applied on track start, stop, seek and before the natural end of a track
+pub struct Fader {
+    sample_rate: u32,
+    ramp: GainRamp,
+    track_end: Option<TrackEndFade>,
+}
+
+impl Fader {
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            ramp: GainRamp::default(),
+            track_end: None,
+        }
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: u32) {
+        self.sample_rate = sample_rate;
+    }
+
+    fn frames(&self, duration: Duration) -> u64 {
+        (duration.as_secs_f64() * self.sample_rate as f64) as u64
+    }
+
+    pub fn fade_in(&mut self, duration: Duration, curve: FadeCurve) {
+        self.ramp.set(0.0);
+        self.ramp.ramp_to(1.0, self.frames(duration), curve);
+    }
+
+    pub fn fade_out(&mut self, duration: Duration, curve: FadeCurve) {
+        self.ramp.ramp_to(0.0, self.frames(duration), curve);
+    }
+
+    pub fn resume(&mut self, duration: Duration, curve: FadeCurve) {
+        self.ramp.ramp_to(1.0, self.frames(duration), curve);
+    }
+
+    /// Fades out the last `duration` of a track that is `length_ms` long, None disables it
+    pub fn set_track_end(&mut self, length_ms: Option<u64>, duration: Duration, curve: FadeCurve) {
+        self.track_end = length_ms
+            .filter(|length| *length > 0 && !duration.is_zero())
+            .map(|end_ms| TrackEndFade {
+                end_ms,
+                duration_ms: (duration.as_millis() as u64).min(end_ms),
+                curve,
+            });
+    }
+
+    /// Applies the envelope, `position` is the frame index of the first frame in `samples`
+    pub fn process(&mut self, samples: &mut [f32], position: u64) {
+        let end = self.track_end.as_ref().map(|end| {
+            let end_frame = end.end_ms * self.sample_rate as u64 / 1000;
+            let length = (end.duration_ms * self.sample_rate as u64 / 1000).max(1);
+            (end_frame, length, end.curve)
+        });
+
+        let frames = (samples.len() / 2) as u64;
+        let reaches_end = end.is_some_and(|(end_frame, length, _)| {
+            position + frames >= end_frame.saturating_sub(length)
+        });
+
+        if self.ramp.is_unity() && !reaches_end {
+            return;
+        }
+
+        for (index, chunk) in samples.chunks_exact_mut(2).enumerate() {
+            let mut gain = self.ramp.next();
+
+            if let Some((end_frame, length, curve)) = end {
+                let frame = position + index as u64;
+                let start = end_frame.saturating_sub(length);
+                if frame >= start {
+                    let progress = (frame - start) as f32 / length as f32;
+                    gain *= 1.0 - curve.shape(progress);
+                }
+            }
+
+            chunk[0] *= gain;
+            chunk[1] *= gain;
+        }
+    }
+}
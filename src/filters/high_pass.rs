@@ -0,0 +1,84 @@
+use super::equalizer::BiquadFilter;
+use super::{AudioFilter, FilterError};
+use std::f64::consts::FRAC_1_SQRT_2;
+
+pub struct HighPassFilter {
+    cutoff_frequency: f64,
+    boost_factor: f64,
+    sample_rate: u32,
+    biquad: BiquadFilter,
+}
+
+impl HighPassFilter {
+    pub fn new(
+        cutoff_frequency: f64,
+        boost_factor: f64,
+        sample_rate: u32,
+    ) -> Result<Self, FilterError> {
+        if cutoff_frequency <= 0.0 || cutoff_frequency >= sample_rate as f64 / 2.0 {
+            return Err(FilterError::InvalidParameter(format!(
+                "HighPass cutoffFrequency must be > 0 and below {}, got {}",
+                sample_rate / 2,
+                cutoff_frequency
+            )));
+        }
+        if boost_factor < 0.0 {
+            return Err(FilterError::InvalidParameter(format!(
+                "HighPass boostFactor must be ≥ 0.0, got {}",
+                boost_factor
+            )));
+        }
+
+        Ok(Self {
+            cutoff_frequency,
+            boost_factor,
+            sample_rate,
+            biquad: BiquadFilter::high_pass(sample_rate as f64, cutoff_frequency, FRAC_1_SQRT_2),
+        })
+    }
+
+    /// Retunes the filter for a new sample rate, the cutoff is kept below the new Nyquist frequency
+    pub fn set_sample_rate(&mut self, sample_rate: u32) {
+        if self.sample_rate == sample_rate {
+            return;
+        }
+
+        let cutoff = self.cutoff_frequency.min(sample_rate as f64 * 0.49);
+        self.sample_rate = sample_rate;
+        self.biquad = BiquadFilter::high_pass(sample_rate as f64, cutoff, FRAC_1_SQRT_2);
+    }
+}
+
+impl AudioFilter for HighPassFilter {
+    fn process(&mut self, samples: &mut [f32], sample_rate: u32) -> Result<(), FilterError> {
+        if samples.len() % 2 != 0 {
+            return Err(FilterError::BufferSizeMismatch);
+        }
+
+        self.set_sample_rate(sample_rate);
+
+        let boost = self.boost_factor as f32;
+
+        for chunk in samples.chunks_exact_mut(2) {
+            let left = self.biquad.process_left(chunk[0]) * boost;
+            let right = self.biquad.process_right(chunk[1]) * boost;
+
+            chunk[0] = left;
+            chunk[1] = right;
+        }
+
+        Ok(())
+    }
+
+    fn is_active(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &'static str {
+        "HighPass"
+    }
+
+    fn reset(&mut self) {
+        self.biquad.reset();
+    }
+}
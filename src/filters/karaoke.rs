@@ -37,7 +37,7 @@ impl KaraokeFilter {
 }
 
 impl AudioFilter for KaraokeFilter {
-    fn process(&mut self, samples: &mut [i16], _sample_rate: u32) -> Result<(), FilterError> {
+    fn process(&mut self, samples: &mut [f32], _sample_rate: u32) -> Result<(), FilterError> {
         if samples.len() % 2 != 0 {
             return Err(FilterError::BufferSizeMismatch);
         }
@@ -55,8 +55,8 @@ impl AudioFilter for KaraokeFilter {
             let new_left = filtered_mid + filtered_side;
             let new_right = filtered_mid - filtered_side;
 
-            chunk[0] = new_left.clamp(i16::MIN as f64, i16::MAX as f64) as i16;
-            chunk[1] = new_right.clamp(i16::MIN as f64, i16::MAX as f64) as i16;
+            chunk[0] = new_left as f32;
+            chunk[1] = new_right as f32;
         }
 
         Ok(())
@@ -0,0 +1,62 @@
+use crate::CONFIG;
+
+/// Limiter threshold in dBFS used when the config does not set one
+const DEFAULT_THRESHOLD_DB: f64 = -1.0;
+
+/// Transparent below the threshold, then bends peaks smoothly so nothing passes full scale
+pub struct SoftLimiter {
+    enabled: bool,
+    threshold: f32,
+}
+
+impl Default for SoftLimiter {
+    fn default() -> Self {
+        let limiter = CONFIG
+            .audio
+            .as_ref()
+            .and_then(|audio| audio.limiter.as_ref());
+
+        Self::new(
+            limiter.is_none_or(|limiter| limiter.enabled),
+            limiter
+                .and_then(|limiter| limiter.threshold)
+                .unwrap_or(DEFAULT_THRESHOLD_DB),
+        )
+    }
+}
+
+impl SoftLimiter {
+    /// `threshold` is in dBFS, clamped between -24 and 0
+    pub fn new(enabled: bool, threshold: f64) -> Self {
+        Self {
+            enabled,
+            threshold: 10_f64.powf(threshold.clamp(-24.0, 0.0) / 20.0) as f32,
+        }
+    }
+
+    #[inline]
+    fn shape(&self, sample: f32) -> f32 {
+        let magnitude = sample.abs();
+        if magnitude <= self.threshold {
+            return sample;
+        }
+
+        let headroom = 1.0 - self.threshold;
+        if headroom <= f32::EPSILON {
+            return sample.clamp(-1.0, 1.0);
+        }
+
+        let limited = self.threshold + headroom * ((magnitude - self.threshold) / headroom).tanh();
+        limited.copysign(sample)
+    }
+
+    pub fn process(&self, samples: &mut [f32]) {
+        if !self.enabled {
+            return;
+        }
+
+        for sample in samples.iter_mut() {
+            *sample = self.shape(*sample);
+        }
+    }
+}
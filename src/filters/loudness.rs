@@ -0,0 +1,300 @@
+use super::smoothing::SmoothedValue;
+use super::{AudioFilter, FilterError};
+use std::f64::consts::PI;
+
+/// Loudness target in LUFS used when neither the player nor the config sets one
+pub const DEFAULT_TARGET_LUFS: f64 = -14.0;
+
+/// Gating block length and the hop between blocks, BS.1770 uses 400ms blocks with 75% overlap
+const SUB_BLOCK_MS: u32 = 100;
+const SUB_BLOCKS_PER_BLOCK: usize = 4;
+
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+const RELATIVE_GATE_LU: f64 = -10.0;
+
+/// Loudness histogram used for gating, 0.1 LU per bin from the absolute gate up to +5 LUFS
+const HISTOGRAM_STEP: f64 = 0.1;
+const HISTOGRAM_BINS: usize = 750;
+
+/// Gated blocks needed before the measurement is trusted, about three seconds of audio
+const MIN_MEASURED_BLOCKS: u64 = 30;
+
+/// Time constant of the gain following the measured loudness, in seconds
+const CONVERGENCE_SECS: f64 = 3.0;
+
+const MIN_GAIN_DB: f64 = -20.0;
+const MAX_GAIN_DB: f64 = 12.0;
+
+#[derive(Clone, Copy, Default)]
+struct Biquad {
+    b0: f64,
+    b1: f64,
+    b2: f64,
+    a1: f64,
+    a2: f64,
+    z1: f64,
+    z2: f64,
+}
+
+impl Biquad {
+    #[inline]
+    fn process(&mut self, input: f64) -> f64 {
+        let output = self.b0 * input + self.z1;
+        self.z1 = self.b1 * input - self.a1 * output + self.z2;
+        self.z2 = self.b2 * input - self.a2 * output;
+        output
+    }
+
+    fn reset(&mut self) {
+        self.z1 = 0.0;
+        self.z2 = 0.0;
+    }
+}
+
+/// The two BS.1770 K-weighting stages, a high shelf modelling the head then a high pass
+fn k_weighting(sample_rate: u32) -> [Biquad; 2] {
+    let fs = sample_rate as f64;
+
+    let f0 = 1681.974450955533;
+    let gain = 3.999843853973347;
+    let q = 0.7071752369554196;
+    let k = (PI * f0 / fs).tan();
+    let vh = 10_f64.powf(gain / 20.0);
+    let vb = vh.powf(0.4996667741545416);
+    let a0 = 1.0 + k / q + k * k;
+    let shelf = Biquad {
+        b0: (vh + vb * k / q + k * k) / a0,
+        b1: 2.0 * (k * k - vh) / a0,
+        b2: (vh - vb * k / q + k * k) / a0,
+        a1: 2.0 * (k * k - 1.0) / a0,
+        a2: (1.0 - k / q + k * k) / a0,
+        ..Default::default()
+    };
+
+    let f0 = 38.13547087602444;
+    let q = 0.5003270373238773;
+    let k = (PI * f0 / fs).tan();
+    let a0 = 1.0 + k / q + k * k;
+    let high_pass = Biquad {
+        b0: 1.0,
+        b1: -2.0,
+        b2: 1.0,
+        a1: 2.0 * (k * k - 1.0) / a0,
+        a2: (1.0 - k / q + k * k) / a0,
+        ..Default::default()
+    };
+
+    [shelf, high_pass]
+}
+
+fn power_to_lufs(power: f64) -> f64 {
+    -0.691 + 10.0 * power.max(1e-20).log10()
+}
+
+/// Measures integrated loudness with K-weighted gating and steers the gain towards a target
+pub struct LoudnessNormalizer {
+    target: f64,
+    source_loudness: Option<f64>,
+
+    sample_rate: u32,
+    filters: [[Biquad; 2]; 2],
+    sub_block_frames: u32,
+    sub_block_sum: f64,
+    sub_block_count: u32,
+    sub_blocks: [f64; SUB_BLOCKS_PER_BLOCK],
+    sub_block_index: usize,
+    sub_blocks_filled: usize,
+
+    histogram_count: Vec<u64>,
+    histogram_power: Vec<f64>,
+    measured_blocks: u64,
+
+    gain_db: f64,
+    gain: SmoothedValue,
+}
+
+impl LoudnessNormalizer {
+    /// `target` is the integrated loudness to normalize to, in LUFS
+    pub fn new(target: f64) -> Result<Self, FilterError> {
+        Self::validate(target)?;
+
+        Ok(Self {
+            target,
+            source_loudness: None,
+            sample_rate: 0,
+            filters: [[Biquad::default(); 2]; 2],
+            sub_block_frames: 0,
+            sub_block_sum: 0.0,
+            sub_block_count: 0,
+            sub_blocks: [0.0; SUB_BLOCKS_PER_BLOCK],
+            sub_block_index: 0,
+            sub_blocks_filled: 0,
+            histogram_count: vec![0; HISTOGRAM_BINS],
+            histogram_power: vec![0.0; HISTOGRAM_BINS],
+            measured_blocks: 0,
+            gain_db: 0.0,
+            gain: SmoothedValue::new(1.0),
+        })
+    }
+
+    pub(super) fn validate(target: f64) -> Result<(), FilterError> {
+        if !(-40.0..=0.0).contains(&target) {
+            return Err(FilterError::InvalidParameter(format!(
+                "Normalization target must be -40–0 LUFS, got {}",
+                target
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn set_target(&mut self, target: f64) -> Result<(), FilterError> {
+        Self::validate(target)?;
+        self.target = target;
+        self.jump_to_source_gain();
+        Ok(())
+    }
+
+    /// Forgets the previous track's measurement, `source_loudness` is the loudness the source reported
+    /// for the new one. The gain carries over and converges from there unless the source knows better
+    pub fn start_track(&mut self, source_loudness: Option<f64>) {
+        self.source_loudness = source_loudness;
+        self.histogram_count.fill(0);
+        self.histogram_power.fill(0.0);
+        self.measured_blocks = 0;
+        self.reset();
+        self.jump_to_source_gain();
+    }
+
+    fn jump_to_source_gain(&mut self) {
+        if let Some(loudness) = self.source_loudness {
+            self.gain_db = (self.target - loudness).clamp(MIN_GAIN_DB, MAX_GAIN_DB);
+            self.gain.set(10_f64.powf(self.gain_db / 20.0));
+        }
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: u32) {
+        if self.sample_rate == sample_rate {
+            return;
+        }
+
+        self.sample_rate = sample_rate;
+        self.filters = [k_weighting(sample_rate); 2];
+        self.sub_block_frames = (sample_rate * SUB_BLOCK_MS / 1000).max(1);
+        self.reset();
+    }
+
+    /// Loudness of the measured blocks that pass both gates
+    fn integrated(&self) -> Option<f64> {
+        let (count, power) = self
+            .histogram_count
+            .iter()
+            .zip(&self.histogram_power)
+            .fold((0, 0.0), |(count, power), (c, p)| (count + c, power + p));
+        if count == 0 {
+            return None;
+        }
+
+        let gate = power_to_lufs(power / count as f64) + RELATIVE_GATE_LU;
+        let first = (((gate - ABSOLUTE_GATE_LUFS) / HISTOGRAM_STEP)
+            .ceil()
+            .max(0.0) as usize)
+            .min(HISTOGRAM_BINS);
+
+        let (count, power) = self.histogram_count[first..]
+            .iter()
+            .zip(&self.histogram_power[first..])
+            .fold((0, 0.0), |(count, power), (c, p)| (count + c, power + p));
+
+        (count > 0).then(|| power_to_lufs(power / count as f64))
+    }
+
+    fn finish_sub_block(&mut self) {
+        self.sub_blocks[self.sub_block_index] =
+            self.sub_block_sum / self.sub_block_count.max(1) as f64;
+        self.sub_block_index = (self.sub_block_index + 1) % SUB_BLOCKS_PER_BLOCK;
+        self.sub_blocks_filled = (self.sub_blocks_filled + 1).min(SUB_BLOCKS_PER_BLOCK);
+        self.sub_block_sum = 0.0;
+        self.sub_block_count = 0;
+
+        if self.sub_blocks_filled == SUB_BLOCKS_PER_BLOCK {
+            let power = self.sub_blocks.iter().sum::<f64>() / SUB_BLOCKS_PER_BLOCK as f64;
+            let loudness = power_to_lufs(power);
+
+            if loudness > ABSOLUTE_GATE_LUFS {
+                let bin = (((loudness - ABSOLUTE_GATE_LUFS) / HISTOGRAM_STEP) as usize)
+                    .min(HISTOGRAM_BINS - 1);
+                self.histogram_count[bin] += 1;
+                self.histogram_power[bin] += power;
+                self.measured_blocks += 1;
+            }
+        }
+
+        self.update_gain();
+    }
+
+    fn update_gain(&mut self) {
+        let loudness = match self.source_loudness {
+            Some(loudness) => Some(loudness),
+            None if self.measured_blocks >= MIN_MEASURED_BLOCKS => self.integrated(),
+            None => None,
+        };
+
+        let Some(loudness) = loudness else {
+            return;
+        };
+
+        let desired = (self.target - loudness).clamp(MIN_GAIN_DB, MAX_GAIN_DB);
+        let step = SUB_BLOCK_MS as f64 / 1000.0;
+        self.gain_db += (desired - self.gain_db) * (1.0 - (-step / CONVERGENCE_SECS).exp());
+        self.gain.set(10_f64.powf(self.gain_db / 20.0));
+    }
+}
+
+impl AudioFilter for LoudnessNormalizer {
+    fn process(&mut self, samples: &mut [f32], sample_rate: u32) -> Result<(), FilterError> {
+        if samples.len() % 2 != 0 {
+            return Err(FilterError::BufferSizeMismatch);
+        }
+
+        self.set_sample_rate(sample_rate);
+
+        for chunk in samples.chunks_exact_mut(2) {
+            // measured before the gain so the normalizer does not chase its own output
+            for (channel, sample) in chunk.iter().enumerate() {
+                let [shelf, high_pass] = &mut self.filters[channel];
+                let weighted = high_pass.process(shelf.process(*sample as f64));
+                self.sub_block_sum += weighted * weighted;
+            }
+            self.sub_block_count += 1;
+
+            if self.sub_block_count >= self.sub_block_frames {
+                self.finish_sub_block();
+            }
+
+            let gain = self.gain.next(sample_rate) as f32;
+            chunk[0] *= gain;
+            chunk[1] *= gain;
+        }
+
+        Ok(())
+    }
+
+    fn is_active(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &'static str {
+        "Normalization"
+    }
+
+    /// Drops the filter state and the partial gating block, the track's measurement is kept across seeks
+    fn reset(&mut self) {
+        for filter in self.filters.iter_mut().flatten() {
+            filter.reset();
+        }
+        self.sub_block_sum = 0.0;
+        self.sub_block_count = 0;
+        self.sub_block_index = 0;
+        self.sub_blocks_filled = 0;
+    }
+}
@@ -1,12 +1,21 @@
 use thiserror::Error;
 
 pub mod channel_mix;
+pub mod chorus;
+pub mod compressor;
 pub mod distortion;
+pub mod echo;
 pub mod equalizer;
+pub mod fade;
+pub mod high_pass;
 pub mod karaoke;
+pub mod limiter;
+pub mod loudness;
 pub mod low_pass;
+pub mod phaser;
 pub mod processor;
 pub mod rotation;
+pub mod smoothing;
 pub mod source;
 pub mod timescale;
 pub mod tremolo;
@@ -23,10 +32,26 @@ pub enum FilterError {
 
     #[error("Buffer size mismatch: expected even number of samples for stereo")]
     BufferSizeMismatch,
+
+    #[error("Filters disabled on this node: {0}")]
+    Disabled(String),
 }
 
+/// Filters work on interleaved stereo f32 samples in the -1.0 to 1.0 range
 pub trait AudioFilter: Send + Sync {
-    fn process(&mut self, samples: &mut [i16], sample_rate: u32) -> Result<(), FilterError>;
+    fn process(&mut self, samples: &mut [f32], sample_rate: u32) -> Result<(), FilterError>;
+    /// Processes a buffer that may change length, filters that keep the length only need `process`
+    fn process_buffer(
+        &mut self,
+        samples: &mut Vec<f32>,
+        sample_rate: u32,
+    ) -> Result<(), FilterError> {
+        self.process(samples, sample_rate)
+    }
+    /// Appends audio still buffered inside the filter once the source has ended, only filters that hold audio back need this
+    fn flush(&mut self, _samples: &mut Vec<f32>, _sample_rate: u32) -> Result<(), FilterError> {
+        Ok(())
+    }
     fn is_active(&self) -> bool;
     fn name(&self) -> &'static str;
     fn reset(&mut self);
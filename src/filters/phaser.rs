@@ -0,0 +1,163 @@
+use super::{AudioFilter, FilterError};
+use std::f64::consts::PI;
+
+pub struct PhaserFilter {
+    stages: usize,
+    rate: f64,
+    depth: f64,
+    feedback: f64,
+    mix: f64,
+    min_frequency: f64,
+    max_frequency: f64,
+    phase: f64,
+    state_left: Vec<f32>,
+    state_right: Vec<f32>,
+    last_left: f32,
+    last_right: f32,
+}
+
+impl PhaserFilter {
+    /// `rate` is in Hz, the all-pass stages sweep between `min_frequency` and `max_frequency`
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        stages: u32,
+        rate: f64,
+        depth: f64,
+        feedback: f64,
+        mix: f64,
+        min_frequency: f64,
+        max_frequency: f64,
+    ) -> Result<Self, FilterError> {
+        if !(2..=12).contains(&stages) || stages % 2 != 0 {
+            return Err(FilterError::InvalidParameter(format!(
+                "Phaser stages must be an even number between 2 and 12, got {}",
+                stages
+            )));
+        }
+        if rate <= 0.0 || rate > 10.0 {
+            return Err(FilterError::InvalidParameter(format!(
+                "Phaser rate must be > 0 and ≤ 10, got {}",
+                rate
+            )));
+        }
+        if !(0.0..=1.0).contains(&depth) {
+            return Err(FilterError::InvalidParameter(format!(
+                "Phaser depth must be 0.0–1.0, got {}",
+                depth
+            )));
+        }
+        if !(0.0..0.95).contains(&feedback) {
+            return Err(FilterError::InvalidParameter(format!(
+                "Phaser feedback must be 0.0–0.95 (exclusive), got {}",
+                feedback
+            )));
+        }
+        if !(0.0..=1.0).contains(&mix) {
+            return Err(FilterError::InvalidParameter(format!(
+                "Phaser mix must be 0.0–1.0, got {}",
+                mix
+            )));
+        }
+        if min_frequency <= 0.0 || max_frequency <= min_frequency {
+            return Err(FilterError::InvalidParameter(format!(
+                "Phaser frequencies must satisfy 0 < min < max, got {} and {}",
+                min_frequency, max_frequency
+            )));
+        }
+
+        Ok(Self {
+            stages: stages as usize,
+            rate,
+            depth,
+            feedback,
+            mix,
+            min_frequency,
+            max_frequency,
+            phase: 0.0,
+            state_left: vec![0.0; stages as usize],
+            state_right: vec![0.0; stages as usize],
+            last_left: 0.0,
+            last_right: 0.0,
+        })
+    }
+
+    /// Runs the input through every first order all-pass stage
+    fn all_pass(states: &mut [f32], coefficient: f32, input: f32) -> f32 {
+        let mut output = input;
+        for state in states.iter_mut() {
+            let stage = -coefficient * output + *state;
+            *state = output + coefficient * stage;
+            output = stage;
+        }
+        output
+    }
+}
+
+impl AudioFilter for PhaserFilter {
+    fn process(&mut self, samples: &mut [f32], sample_rate: u32) -> Result<(), FilterError> {
+        if samples.len() % 2 != 0 {
+            return Err(FilterError::BufferSizeMismatch);
+        }
+
+        let phase_inc = 2.0 * PI * self.rate / sample_rate as f64;
+        let nyquist = sample_rate as f64 / 2.0;
+        let min = self.min_frequency.min(nyquist * 0.95);
+        let max = self.max_frequency.min(nyquist * 0.95);
+        let feedback = self.feedback as f32;
+        let mix = self.mix as f32;
+
+        for chunk in samples.chunks_exact_mut(2) {
+            let lfo = 0.5 + 0.5 * self.phase.sin() * self.depth;
+            // sweep exponentially so the movement sounds even across the range
+            let frequency = min * (max / min).powf(lfo);
+            let tan = (PI * frequency / sample_rate as f64).tan();
+            let coefficient = ((tan - 1.0) / (tan + 1.0)) as f32;
+
+            let left = chunk[0];
+            let right = chunk[1];
+
+            let wet_left = Self::all_pass(
+                &mut self.state_left,
+                coefficient,
+                left + self.last_left * feedback,
+            );
+            let wet_right = Self::all_pass(
+                &mut self.state_right,
+                coefficient,
+                right + self.last_right * feedback,
+            );
+
+            self.last_left = wet_left;
+            self.last_right = wet_right;
+
+            let out_left = left * (1.0 - mix) + wet_left * mix;
+            let out_right = right * (1.0 - mix) + wet_right * mix;
+
+            chunk[0] = out_left;
+            chunk[1] = out_right;
+
+            self.phase += phase_inc;
+            if self.phase > 2.0 * PI {
+                self.phase -= 2.0 * PI;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn is_active(&self) -> bool {
+        self.mix > f64::EPSILON && self.stages > 0
+    }
+
+    fn name(&self) -> &'static str {
+        "Phaser"
+    }
+
+    fn reset(&mut self) {
+        self.phase = 0.0;
+        self.state_left.fill(0.0);
+        self.state_right.fill(0.0);
+        self.last_left = 0.0;
+        self.last_right = 0.0;
+    }
+}
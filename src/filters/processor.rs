@@ -1,15 +1,32 @@
 use super::channel_mix::ChannelMixFilter;
+use super::chorus::ChorusFilter;
+use super::compressor::CompressorFilter;
 use super::distortion::DistortionFilter;
+use super::echo::EchoFilter;
 use super::equalizer::EqualizerFilter;
+use super::fade::{FadeCurve, Fader, GainRamp};
+use super::high_pass::HighPassFilter;
 use super::karaoke::KaraokeFilter;
+use super::limiter::SoftLimiter;
+use super::loudness::{DEFAULT_TARGET_LUFS, LoudnessNormalizer};
 use super::low_pass::LowPassFilter;
+use super::phaser::PhaserFilter;
 use super::rotation::RotationFilter;
 use super::timescale::TimescaleFilter;
 use super::tremolo::TremoloFilter;
 use super::vibrato::VibratoFilter;
 use super::volume::VolumeFilter;
 use super::{AudioFilter, FilterError};
-use crate::models::LavalinkFilters;
+use crate::CONFIG;
+use crate::models::{LavalinkFilters, Normalization, PluginFilters, SkipSilence};
+use crate::util::config::FiltersEnabled;
+use std::time::Duration;
+
+/// Silence threshold in dBFS used when neither the player nor the config sets one
+const DEFAULT_SILENCE_THRESHOLD_DB: f64 = -50.0;
+
+/// How long a player volume change takes to settle in the chain
+const PLAYER_VOLUME_RAMP: Duration = Duration::from_millis(20);
 
 pub struct FilterChain {
     volume: Option<VolumeFilter>,
@@ -22,6 +39,23 @@ pub struct FilterChain {
     karaoke: Option<KaraokeFilter>,
     channel_mix: Option<ChannelMixFilter>,
     low_pass: Option<LowPassFilter>,
+    high_pass: Option<HighPassFilter>,
+    chorus: Option<ChorusFilter>,
+    phaser: Option<PhaserFilter>,
+    echo: Option<EchoFilter>,
+    compressor: Option<CompressorFilter>,
+    loudness: Option<LoudnessNormalizer>,
+    /// Peak level below which leading and trailing audio is cut, None keeps silence
+    silence_threshold: Option<f32>,
+
+    config: LavalinkFilters,
+    plugin_config: PluginFilters,
+
+    fader: Fader,
+    ducking: GainRamp,
+    /// Player volume, applied here rather than by songbird so the limiter catches anything above 100
+    player_volume: GainRamp,
+    limiter: SoftLimiter,
 
     sample_rate: u32,
     enabled: bool,
@@ -40,113 +74,512 @@ impl FilterChain {
             karaoke: None,
             channel_mix: None,
             low_pass: None,
+            high_pass: None,
+            chorus: None,
+            phaser: None,
+            echo: None,
+            compressor: None,
+            loudness: Self::normalization_target(None)
+                .and_then(|target| LoudnessNormalizer::new(target).ok()),
+            silence_threshold: Self::silence_threshold_from(None).ok().flatten(),
+            config: LavalinkFilters::default(),
+            plugin_config: PluginFilters::default(),
+            fader: Fader::new(sample_rate),
+            ducking: GainRamp::default(),
+            player_volume: GainRamp::default(),
+            limiter: SoftLimiter::default(),
             sample_rate,
             enabled: true,
         }
     }
 
-    pub fn update_from_config(&mut self, config: &LavalinkFilters) -> Result<(), FilterError> {
-        self.volume = match config.volume {
-            Some(vol) => Some(VolumeFilter::new(vol)?),
-            None => None,
-        };
+    /// Every filter name paired with whether this node allows it, volume can't be disabled and every
+    /// filter is allowed when the config has no filters section
+    pub fn availability() -> Vec<(&'static str, bool)> {
+        const FILTERS: [(&str, fn(&FiltersEnabled) -> bool); 15] = [
+            ("volume", |_| true),
+            ("equalizer", |enabled| enabled.equalizer),
+            ("timescale", |enabled| enabled.timescale),
+            ("tremolo", |enabled| enabled.tremolo),
+            ("vibrato", |enabled| enabled.vibrato),
+            ("rotation", |enabled| enabled.rotation),
+            ("distortion", |enabled| enabled.distortion),
+            ("channelMix", |enabled| enabled.channel_mix),
+            ("lowPass", |enabled| enabled.lowpass),
+            ("karaoke", |enabled| enabled.karaoke),
+            ("highPass", |enabled| enabled.highpass),
+            ("chorus", |enabled| enabled.chorus),
+            ("compressor", |enabled| enabled.compressor),
+            ("echo", |enabled| enabled.echo),
+            ("phaser", |enabled| enabled.phaser),
+        ];
 
-        self.equalizer = match &config.equalizer {
-            Some(bands) if !bands.is_empty() => {
-                Some(EqualizerFilter::from_bands(bands, self.sample_rate)?)
-            }
-            _ => None,
+        let enabled = CONFIG.filters.as_ref().map(|filters| &filters.enabled);
+
+        FILTERS
+            .iter()
+            .map(|(name, allowed)| (*name, enabled.is_none_or(allowed)))
+            .collect()
+    }
+
+    /// Loudness target in LUFS when normalization is on, the player's pluginFilters override the config default
+    fn normalization_target(normalization: Option<&Normalization>) -> Option<f64> {
+        let default = CONFIG
+            .audio
+            .as_ref()
+            .and_then(|audio| audio.normalization.as_ref());
+
+        let enabled = match normalization {
+            Some(normalization) => normalization.enabled.unwrap_or(true),
+            None => default.is_some_and(|default| default.enabled),
         };
 
-        self.timescale = match &config.timescale {
-            Some(ts) => Some(TimescaleFilter::new(
-                ts.speed.unwrap_or(1.0),
-                ts.pitch.unwrap_or(1.0),
-                ts.rate.unwrap_or(1.0),
-            )?),
-            None => None,
+        enabled.then(|| {
+            normalization
+                .and_then(|normalization| normalization.target)
+                .or(default.and_then(|default| default.target))
+                .unwrap_or(DEFAULT_TARGET_LUFS)
+        })
+    }
+
+    /// Linear silence threshold when skipping silence is on, the player's pluginFilters override the config default
+    fn silence_threshold_from(
+        skip_silence: Option<&SkipSilence>,
+    ) -> Result<Option<f32>, FilterError> {
+        let default = CONFIG
+            .audio
+            .as_ref()
+            .and_then(|audio| audio.skip_silence.as_ref());
+
+        let enabled = match skip_silence {
+            Some(skip_silence) => skip_silence.enabled.unwrap_or(true),
+            None => default.is_some_and(|default| default.enabled),
         };
 
-        self.tremolo = match &config.tremolo {
-            Some(t) => Some(TremoloFilter::new(
-                t.frequency.unwrap_or(2.0),
-                t.depth.unwrap_or(0.5),
-            )?),
-            None => None,
+        if !enabled {
+            return Ok(None);
+        }
+
+        let threshold = skip_silence
+            .and_then(|skip_silence| skip_silence.threshold)
+            .or(default.and_then(|default| default.threshold))
+            .unwrap_or(DEFAULT_SILENCE_THRESHOLD_DB);
+
+        if !(-90.0..=0.0).contains(&threshold) {
+            return Err(FilterError::InvalidParameter(format!(
+                "Skip silence threshold must be -90–0 dB, got {}",
+                threshold
+            )));
+        }
+
+        Ok(Some(10_f64.powf(threshold / 20.0) as f32))
+    }
+
+    /// Names of the filters set in `config` that are disabled on this node
+    pub fn disabled_filters(config: &LavalinkFilters) -> Vec<&'static str> {
+        let plugin_filters = config
+            .plugin_filters
+            .as_ref()
+            .and_then(|value| serde_json::from_value::<PluginFilters>(value.clone()).ok())
+            .unwrap_or_default();
+
+        let requested = [
+            ("equalizer", config.equalizer.is_some()),
+            ("timescale", config.timescale.is_some()),
+            ("tremolo", config.tremolo.is_some()),
+            ("vibrato", config.vibrato.is_some()),
+            ("rotation", config.rotation.is_some()),
+            ("distortion", config.distortion.is_some()),
+            ("channelMix", config.channel_mix.is_some()),
+            ("lowPass", config.low_pass.is_some()),
+            ("karaoke", config.karaoke.is_some()),
+            ("highPass", plugin_filters.high_pass.is_some()),
+            ("chorus", plugin_filters.chorus.is_some()),
+            ("compressor", plugin_filters.compressor.is_some()),
+            ("echo", plugin_filters.echo.is_some()),
+            ("phaser", plugin_filters.phaser.is_some()),
+        ];
+
+        let availability = Self::availability();
+
+        requested
+            .into_iter()
+            .filter(|(name, present)| {
+                *present
+                    && availability
+                        .iter()
+                        .any(|(available, enabled)| available == name && !enabled)
+            })
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    /// Applies `config`, keeping the state of filters whose parameters did not change.
+    /// Volume, equalizer, tremolo, vibrato, rotation, channel mix and low pass glide to new values.
+    /// Every parameter is checked before anything is applied, so a rejected update leaves the chain as it was
+    pub fn update_from_config(&mut self, config: &LavalinkFilters) -> Result<(), FilterError> {
+        let disabled = Self::disabled_filters(config);
+
+        if !disabled.is_empty() {
+            return Err(FilterError::Disabled(disabled.join(", ")));
+        }
+
+        let plugin_filters = match &config.plugin_filters {
+            Some(value) => serde_json::from_value::<PluginFilters>(value.clone())
+                .map_err(|e| FilterError::InvalidParameter(format!("pluginFilters: {}", e)))?,
+            None => PluginFilters::default(),
         };
 
-        self.vibrato = match &config.vibrato {
-            Some(v) => Some(VibratoFilter::new(
+        let volume = config.volume.unwrap_or(1.0);
+        VolumeFilter::validate(volume)?;
+
+        let bands = config.equalizer.as_deref().unwrap_or_default();
+        EqualizerFilter::validate(bands)?;
+
+        let (frequency, depth) = config
+            .tremolo
+            .as_ref()
+            .map(|t| (t.frequency.unwrap_or(2.0), t.depth.unwrap_or(0.5)))
+            .unwrap_or((2.0, 0.0));
+        TremoloFilter::validate(frequency, depth)?;
+
+        if let Some(v) = &config.vibrato {
+            VibratoFilter::validate(v.frequency.unwrap_or(2.0), v.depth.unwrap_or(0.5))?;
+        }
+
+        let mix = config
+            .channel_mix
+            .as_ref()
+            .map(|cm| {
+                (
+                    cm.left_to_left.unwrap_or(1.0),
+                    cm.left_to_right.unwrap_or(0.0),
+                    cm.right_to_left.unwrap_or(0.0),
+                    cm.right_to_right.unwrap_or(1.0),
+                )
+            })
+            .unwrap_or((1.0, 0.0, 0.0, 1.0));
+        ChannelMixFilter::validate(mix.0, mix.1, mix.2, mix.3)?;
+
+        if let Some(lp) = &config.low_pass {
+            LowPassFilter::validate(lp.smoothing.unwrap_or(1.0))?;
+        }
+
+        let normalization_target =
+            Self::normalization_target(plugin_filters.normalization.as_ref());
+        if let Some(target) = normalization_target {
+            LoudnessNormalizer::validate(target)?;
+        }
+
+        let silence_threshold = Self::silence_threshold_from(plugin_filters.skip_silence.as_ref())?;
+
+        // filters without smoothing are rebuilt when their parameters change, None leaves them as they are
+        let timescale = (self.config.timescale != config.timescale)
+            .then(|| {
+                config
+                    .timescale
+                    .as_ref()
+                    .map(|ts| {
+                        TimescaleFilter::new(
+                            ts.speed.unwrap_or(1.0),
+                            ts.pitch.unwrap_or(1.0),
+                            ts.rate.unwrap_or(1.0),
+                        )
+                    })
+                    .transpose()
+            })
+            .transpose()?;
+
+        let distortion = (self.config.distortion != config.distortion)
+            .then(|| {
+                config
+                    .distortion
+                    .as_ref()
+                    .map(|d| {
+                        DistortionFilter::new(
+                            d.sin_offset.unwrap_or(0.0),
+                            d.sin_scale.unwrap_or(1.0),
+                            d.cos_offset.unwrap_or(0.0),
+                            d.cos_scale.unwrap_or(1.0),
+                            d.tan_offset.unwrap_or(0.0),
+                            d.tan_scale.unwrap_or(1.0),
+                            d.offset.unwrap_or(0.0),
+                            d.scale.unwrap_or(1.0),
+                        )
+                    })
+                    .transpose()
+            })
+            .transpose()?;
+
+        let karaoke = (self.config.karaoke != config.karaoke)
+            .then(|| {
+                config
+                    .karaoke
+                    .as_ref()
+                    .map(|k| {
+                        KaraokeFilter::new(
+                            k.level.unwrap_or(1.0),
+                            k.mono_level.unwrap_or(1.0),
+                            k.filter_band.unwrap_or(220.0),
+                            k.filter_width.unwrap_or(100.0),
+                        )
+                    })
+                    .transpose()
+            })
+            .transpose()?;
+
+        let high_pass = (self.plugin_config.high_pass != plugin_filters.high_pass)
+            .then(|| {
+                plugin_filters
+                    .high_pass
+                    .as_ref()
+                    .map(|hp| {
+                        HighPassFilter::new(
+                            hp.cutoff_frequency.unwrap_or(200.0),
+                            hp.boost_factor.unwrap_or(1.0),
+                            self.sample_rate,
+                        )
+                    })
+                    .transpose()
+            })
+            .transpose()?;
+
+        let chorus = (self.plugin_config.chorus != plugin_filters.chorus)
+            .then(|| {
+                plugin_filters
+                    .chorus
+                    .as_ref()
+                    .map(|c| {
+                        ChorusFilter::new(
+                            c.rate.unwrap_or(1.5),
+                            c.depth.unwrap_or(0.5),
+                            c.delay.unwrap_or(25.0),
+                            c.mix.unwrap_or(0.5),
+                            c.feedback.unwrap_or(0.0),
+                        )
+                    })
+                    .transpose()
+            })
+            .transpose()?;
+
+        let phaser = (self.plugin_config.phaser != plugin_filters.phaser)
+            .then(|| {
+                plugin_filters
+                    .phaser
+                    .as_ref()
+                    .map(|p| {
+                        PhaserFilter::new(
+                            p.stages.unwrap_or(4),
+                            p.rate.unwrap_or(0.5),
+                            p.depth.unwrap_or(1.0),
+                            p.feedback.unwrap_or(0.5),
+                            p.mix.unwrap_or(0.5),
+                            p.min_frequency.unwrap_or(100.0),
+                            p.max_frequency.unwrap_or(2500.0),
+                        )
+                    })
+                    .transpose()
+            })
+            .transpose()?;
+
+        let echo = (self.plugin_config.echo != plugin_filters.echo)
+            .then(|| {
+                plugin_filters
+                    .echo
+                    .as_ref()
+                    .map(|e| {
+                        EchoFilter::new(
+                            e.echo_length
+                                .or(e.delay.map(|delay| delay / 1000.0))
+                                .unwrap_or(1.0),
+                            e.decay.unwrap_or(0.5),
+                            e.mix.unwrap_or(0.5),
+                            self.sample_rate,
+                        )
+                    })
+                    .transpose()
+            })
+            .transpose()?;
+
+        let compressor = (self.plugin_config.compressor != plugin_filters.compressor)
+            .then(|| {
+                plugin_filters
+                    .compressor
+                    .as_ref()
+                    .map(|c| {
+                        CompressorFilter::new(
+                            c.threshold.unwrap_or(-20.0),
+                            c.ratio.unwrap_or(4.0),
+                            c.attack.unwrap_or(5.0),
+                            c.release.unwrap_or(50.0),
+                            c.gain.unwrap_or(0.0),
+                        )
+                    })
+                    .transpose()
+            })
+            .transpose()?;
+
+        // nothing below can fail any more
+        match &mut self.volume {
+            Some(filter) => filter.set_volume(volume)?,
+            None if config.volume.is_some() => {
+                let mut filter = VolumeFilter::new(1.0)?;
+                filter.set_volume(volume)?;
+                self.volume = Some(filter);
+            }
+            None => {}
+        }
+
+        match &mut self.equalizer {
+            Some(filter) => filter.set_bands(bands)?,
+            None if !bands.is_empty() => {
+                let mut filter = EqualizerFilter::new(self.sample_rate);
+                filter.set_bands(bands)?;
+                self.equalizer = Some(filter);
+            }
+            None => {}
+        }
+
+        if let Some(timescale) = timescale {
+            self.timescale = timescale;
+        }
+
+        match &mut self.tremolo {
+            Some(filter) => filter.set_params(frequency, depth)?,
+            None if config.tremolo.is_some() => {
+                let mut filter = TremoloFilter::new(frequency, 0.0)?;
+                filter.set_params(frequency, depth)?;
+                self.tremolo = Some(filter);
+            }
+            None => {}
+        }
+
+        self.vibrato = match (&config.vibrato, self.vibrato.take()) {
+            (Some(v), Some(mut filter)) => {
+                filter.set_params(v.frequency.unwrap_or(2.0), v.depth.unwrap_or(0.5))?;
+                Some(filter)
+            }
+            (Some(v), None) => Some(VibratoFilter::new(
                 v.frequency.unwrap_or(2.0),
                 v.depth.unwrap_or(0.5),
             )?),
-            None => None,
+            (None, _) => None,
         };
 
-        self.rotation = match &config.rotation {
-            Some(r) => Some(RotationFilter::new(r.rotation_hz.unwrap_or(0.0))?),
-            None => None,
+        self.rotation = match (&config.rotation, self.rotation.take()) {
+            (Some(r), Some(mut filter)) => {
+                filter.set_rotation(r.rotation_hz.unwrap_or(0.0));
+                Some(filter)
+            }
+            (Some(r), None) => Some(RotationFilter::new(r.rotation_hz.unwrap_or(0.0))?),
+            (None, _) => None,
         };
 
-        self.distortion = match &config.distortion {
-            Some(d) => Some(DistortionFilter::new(
-                d.sin_offset.unwrap_or(0.0),
-                d.sin_scale.unwrap_or(1.0),
-                d.cos_offset.unwrap_or(0.0),
-                d.cos_scale.unwrap_or(1.0),
-                d.tan_offset.unwrap_or(0.0),
-                d.tan_scale.unwrap_or(1.0),
-                d.offset.unwrap_or(0.0),
-                d.scale.unwrap_or(1.0),
-            )?),
-            None => None,
-        };
+        if let Some(distortion) = distortion {
+            self.distortion = distortion;
+        }
 
-        self.karaoke = match &config.karaoke {
-            Some(k) => Some(KaraokeFilter::new(
-                k.level.unwrap_or(1.0),
-                k.mono_level.unwrap_or(1.0),
-                k.filter_band.unwrap_or(220.0),
-                k.filter_width.unwrap_or(100.0),
-            )?),
-            None => None,
-        };
+        if let Some(karaoke) = karaoke {
+            self.karaoke = karaoke;
+        }
 
-        self.channel_mix = match &config.channel_mix {
-            Some(cm) => Some(ChannelMixFilter::new(
-                cm.left_to_left.unwrap_or(1.0),
-                cm.left_to_right.unwrap_or(0.0),
-                cm.right_to_left.unwrap_or(0.0),
-                cm.right_to_right.unwrap_or(1.0),
-            )?),
-            None => None,
+        match &mut self.channel_mix {
+            Some(filter) => filter.set_params(mix.0, mix.1, mix.2, mix.3)?,
+            None if config.channel_mix.is_some() => {
+                let mut filter = ChannelMixFilter::new(1.0, 0.0, 0.0, 1.0)?;
+                filter.set_params(mix.0, mix.1, mix.2, mix.3)?;
+                self.channel_mix = Some(filter);
+            }
+            None => {}
+        }
+
+        self.low_pass = match (&config.low_pass, self.low_pass.take()) {
+            (Some(lp), Some(mut filter)) => {
+                filter.set_smoothing(lp.smoothing.unwrap_or(1.0))?;
+                Some(filter)
+            }
+            (Some(lp), None) => Some(LowPassFilter::new(lp.smoothing.unwrap_or(1.0))?),
+            (None, _) => None,
         };
 
-        self.low_pass = match &config.low_pass {
-            Some(lp) => Some(LowPassFilter::new(lp.smoothing.unwrap_or(1.0))?),
-            None => None,
+        if let Some(high_pass) = high_pass {
+            self.high_pass = high_pass;
+        }
+
+        if let Some(chorus) = chorus {
+            self.chorus = chorus;
+        }
+
+        if let Some(phaser) = phaser {
+            self.phaser = phaser;
+        }
+
+        if let Some(echo) = echo {
+            self.echo = echo;
+        }
+
+        if let Some(compressor) = compressor {
+            self.compressor = compressor;
+        }
+
+        self.loudness = match (normalization_target, self.loudness.take()) {
+            (Some(target), Some(mut filter)) => {
+                filter.set_target(target)?;
+                Some(filter)
+            }
+            (Some(target), None) => Some(LoudnessNormalizer::new(target)?),
+            (None, _) => None,
         };
 
+        self.silence_threshold = silence_threshold;
+
+        self.config = config.clone();
+        self.plugin_config = plugin_filters;
+
         Ok(())
     }
 
-    pub fn process(&mut self, samples: &mut [i16]) -> Result<(), FilterError> {
-        if !self.enabled || samples.is_empty() {
+    /// Runs the active filters, the fade envelope, the player volume then the limiter, `position` is the frame
+    /// index of the first frame. Timescale may grow or shrink `samples`, and it may come back empty while the
+    /// stretcher buffers. With no active filters the samples are left untouched unless a fade, duck or volume
+    /// change applies
+    pub fn process(&mut self, samples: &mut Vec<f32>, position: u64) -> Result<(), FilterError> {
+        if samples.is_empty() {
             return Ok(());
         }
 
+        // fades and ducking only ever lower the gain, so the limiter is only needed after filters or a boost
+        let filtering = self.enabled && self.has_active_filters();
+        if filtering {
+            self.process_filters(samples)?;
+        }
+
+        self.fader.process(samples, position);
+        self.ducking.apply(samples);
+        self.player_volume.apply(samples);
+
+        if filtering || self.player_volume.peak() > 1.0 {
+            self.limiter.process(samples);
+        }
+
+        Ok(())
+    }
+
+    fn process_filters(&mut self, samples: &mut Vec<f32>) -> Result<(), FilterError> {
         let sr = self.sample_rate;
 
         macro_rules! apply {
             ($filter:expr) => {
                 if let Some(f) = &mut $filter {
                     if f.is_active() {
-                        f.process(samples, sr)?;
+                        f.process_buffer(samples, sr)?;
                     }
                 }
             };
         }
 
+        // measures the source before any other filter shapes it
+        apply!(self.loudness);
         apply!(self.volume);
         apply!(self.equalizer);
         apply!(self.timescale);
@@ -157,10 +590,85 @@ impl FilterChain {
         apply!(self.karaoke);
         apply!(self.channel_mix);
         apply!(self.low_pass);
+        apply!(self.high_pass);
+        apply!(self.chorus);
+        apply!(self.phaser);
+        apply!(self.echo);
+        apply!(self.compressor);
+
+        Ok(())
+    }
+
+    /// Drains the audio filters still hold once the source has ended, `position` is the source frame it ended at
+    pub fn flush(&mut self, position: u64) -> Result<Vec<f32>, FilterError> {
+        let mut samples = Vec::new();
+
+        if !(self.enabled && self.has_active_filters()) {
+            return Ok(samples);
+        }
+
+        self.flush_filters(&mut samples)?;
+
+        if !samples.is_empty() {
+            self.fader.process(&mut samples, position);
+            self.ducking.apply(&mut samples);
+            self.player_volume.apply(&mut samples);
+            self.limiter.process(&mut samples);
+        }
+
+        Ok(samples)
+    }
+
+    /// Flushes every filter in processing order, running what earlier filters flushed through the later ones
+    fn flush_filters(&mut self, samples: &mut Vec<f32>) -> Result<(), FilterError> {
+        let sr = self.sample_rate;
+
+        macro_rules! flush {
+            ($filter:expr) => {
+                if let Some(f) = &mut $filter {
+                    if f.is_active() {
+                        if !samples.is_empty() {
+                            f.process_buffer(samples, sr)?;
+                        }
+                        f.flush(samples, sr)?;
+                    }
+                }
+            };
+        }
+
+        flush!(self.loudness);
+        flush!(self.volume);
+        flush!(self.equalizer);
+        flush!(self.timescale);
+        flush!(self.tremolo);
+        flush!(self.vibrato);
+        flush!(self.rotation);
+        flush!(self.distortion);
+        flush!(self.karaoke);
+        flush!(self.channel_mix);
+        flush!(self.low_pass);
+        flush!(self.high_pass);
+        flush!(self.chorus);
+        flush!(self.phaser);
+        flush!(self.echo);
+        flush!(self.compressor);
 
         Ok(())
     }
 
+    /// True when processing would leave the audio untouched, so the source can skip the chain entirely
+    pub fn is_idle(&self) -> bool {
+        !(self.enabled && self.has_active_filters())
+            && self.ducking.is_unity()
+            && self.player_volume.is_unity()
+            && self.silence_threshold().is_none()
+    }
+
+    /// Peak level below which the source cuts leading and trailing audio, None when silence is kept
+    pub fn silence_threshold(&self) -> Option<f32> {
+        self.silence_threshold.filter(|_| self.enabled)
+    }
+
     pub fn has_active_filters(&self) -> bool {
         macro_rules! check {
             ($filter:expr) => {
@@ -181,6 +689,12 @@ impl FilterChain {
         check!(self.karaoke);
         check!(self.channel_mix);
         check!(self.low_pass);
+        check!(self.high_pass);
+        check!(self.chorus);
+        check!(self.phaser);
+        check!(self.echo);
+        check!(self.compressor);
+        check!(self.loudness);
         false
     }
 
@@ -195,6 +709,15 @@ impl FilterChain {
         self.karaoke = None;
         self.channel_mix = None;
         self.low_pass = None;
+        self.high_pass = None;
+        self.chorus = None;
+        self.phaser = None;
+        self.echo = None;
+        self.compressor = None;
+        self.loudness = Self::normalization_target(None)
+            .and_then(|target| LoudnessNormalizer::new(target).ok());
+        self.config = LavalinkFilters::default();
+        self.plugin_config = PluginFilters::default();
     }
 
     pub fn reset_state(&mut self) {
@@ -215,6 +738,19 @@ impl FilterChain {
         reset!(self.karaoke);
         reset!(self.channel_mix);
         reset!(self.low_pass);
+        reset!(self.high_pass);
+        reset!(self.chorus);
+        reset!(self.phaser);
+        reset!(self.echo);
+        reset!(self.compressor);
+        reset!(self.loudness);
+    }
+
+    /// Starts a new loudness measurement, `source_loudness` is what the source reported for the track in LUFS
+    pub fn start_track(&mut self, source_loudness: Option<f64>) {
+        if let Some(loudness) = &mut self.loudness {
+            loudness.start_track(source_loudness);
+        }
     }
 
     pub fn set_enabled(&mut self, enabled: bool) {
@@ -225,7 +761,45 @@ impl FilterChain {
         self.enabled
     }
 
+    /// Retunes the fader and every filter whose coefficients or buffers depend on the sample rate
     pub fn set_sample_rate(&mut self, sample_rate: u32) {
         self.sample_rate = sample_rate;
+        self.fader.set_sample_rate(sample_rate);
+
+        if let Some(equalizer) = &mut self.equalizer {
+            equalizer.set_sample_rate(sample_rate);
+        }
+        if let Some(high_pass) = &mut self.high_pass {
+            high_pass.set_sample_rate(sample_rate);
+        }
+        if let Some(echo) = &mut self.echo {
+            echo.set_sample_rate(sample_rate);
+        }
+    }
+
+    /// Ramps the ducking gain to `gain` over `duration`, 1.0 releases the duck
+    pub fn duck(&mut self, gain: f32, duration: Duration, curve: FadeCurve) {
+        let frames = (duration.as_secs_f64() * self.sample_rate as f64) as u64;
+        self.ducking.ramp_to(gain.clamp(0.0, 1.0), frames, curve);
+    }
+
+    /// Ramps the player volume to `volume`, 1.0 being 100, over a short step so the change doesn't click
+    pub fn set_player_volume(&mut self, volume: f32) {
+        let frames = (PLAYER_VOLUME_RAMP.as_secs_f64() * self.sample_rate as f64) as u64;
+        self.player_volume
+            .ramp_to(volume.max(0.0), frames, FadeCurve::Linear);
+    }
+
+    /// How many source frames get played per output frame, only timescale changes this
+    pub fn playback_rate(&self) -> f64 {
+        self.timescale
+            .as_ref()
+            .filter(|timescale| self.enabled && timescale.is_active())
+            .map(|timescale| timescale.playback_rate())
+            .unwrap_or(1.0)
+    }
+
+    pub fn fader_mut(&mut self) -> &mut Fader {
+        &mut self.fader
     }
 }
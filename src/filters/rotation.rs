@@ -13,10 +13,15 @@ impl RotationFilter {
             phase: 0.0,
         })
     }
+
+    /// Changes the rotation speed without jumping back to the start of the rotation
+    pub fn set_rotation(&mut self, rotation_hz: f64) {
+        self.rotation_hz = rotation_hz;
+    }
 }
 
 impl AudioFilter for RotationFilter {
-    fn process(&mut self, samples: &mut [i16], sample_rate: u32) -> Result<(), FilterError> {
+    fn process(&mut self, samples: &mut [f32], sample_rate: u32) -> Result<(), FilterError> {
         let phase_inc = 2.0 * PI * self.rotation_hz / sample_rate as f64;
 
         for chunk in samples.chunks_exact_mut(2) {
@@ -30,8 +35,8 @@ impl AudioFilter for RotationFilter {
             let left_out = mono * pan_left;
             let right_out = mono * pan_right;
 
-            chunk[0] = left_out.clamp(i16::MIN as f64, i16::MAX as f64) as i16;
-            chunk[1] = right_out.clamp(i16::MIN as f64, i16::MAX as f64) as i16;
+            chunk[0] = left_out as f32;
+            chunk[1] = right_out as f32;
 
             self.phase += phase_inc;
             if self.phase > 2.0 * PI {
@@ -0,0 +1,72 @@
+/// How long a parameter change takes to glide to its new value
+pub const PARAMETER_RAMP_MS: u32 = 50;
+
+/// Filter parameter that glides linearly to a new value instead of jumping
+#[derive(Clone, Copy, Debug)]
+pub struct SmoothedValue {
+    current: f64,
+    target: f64,
+    step: f64,
+    remaining: u32,
+    pending: bool,
+}
+
+impl SmoothedValue {
+    pub fn new(value: f64) -> Self {
+        Self {
+            current: value,
+            target: value,
+            step: 0.0,
+            remaining: 0,
+            pending: false,
+        }
+    }
+
+    /// Starts gliding towards `target`, the ramp length is resolved on the next processed frame
+    pub fn set(&mut self, target: f64) {
+        if (target - self.target).abs() > f64::EPSILON {
+            self.target = target;
+            self.pending = true;
+        }
+    }
+
+    pub fn current(&self) -> f64 {
+        self.current
+    }
+
+    pub fn target(&self) -> f64 {
+        self.target
+    }
+
+    pub fn is_ramping(&self) -> bool {
+        self.pending || self.remaining > 0
+    }
+
+    /// Moves the value `frames` frames ahead and returns it
+    pub fn advance(&mut self, frames: u32, sample_rate: u32) -> f64 {
+        if self.pending {
+            self.pending = false;
+            self.remaining = (sample_rate * PARAMETER_RAMP_MS / 1000).max(1);
+            self.step = (self.target - self.current) / self.remaining as f64;
+        }
+
+        if self.remaining == 0 {
+            return self.current;
+        }
+
+        let frames = frames.min(self.remaining);
+        self.remaining -= frames;
+        self.current = if self.remaining == 0 {
+            self.target
+        } else {
+            self.current + self.step * frames as f64
+        };
+
+        self.current
+    }
+
+    #[inline]
+    pub fn next(&mut self, sample_rate: u32) -> f64 {
+        self.advance(1, sample_rate)
+    }
+}
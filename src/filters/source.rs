@@ -1,187 +1,519 @@
 use crate::filters::processor::FilterChain;
 use async_trait::async_trait;
 use songbird::input::codecs::{get_codec_registry, get_probe};
-use songbird::input::{AudioStream, AudioStreamError, Compose};
+use songbird::input::{AudioStream, AudioStreamError, AuxMetadata, Compose, Parsed, RawAdapter};
+use std::collections::VecDeque;
 use std::io::{self, Read, Seek, SeekFrom};
 use std::sync::{Arc, Mutex};
-use symphonia::core::audio::SampleBuffer;
-use symphonia::core::codecs::{CODEC_TYPE_NULL, DecoderOptions};
-use symphonia::core::formats::{FormatOptions, FormatReader};
+use symphonia::core::audio::{Channels, SampleBuffer};
+use symphonia::core::codecs::{
+    CODEC_TYPE_NULL, CODEC_TYPE_PCM_F32LE, CodecParameters, Decoder, DecoderOptions,
+};
+use symphonia::core::errors::{Error as SymphoniaError, Result as SymphoniaResult};
+use symphonia::core::formats::{
+    Cue, FormatOptions, FormatReader, Packet, SeekMode, SeekTo, SeekedTo, Track,
+};
 use symphonia::core::io::{MediaSource, MediaSourceStream};
-use symphonia::core::meta::MetadataOptions;
+use symphonia::core::meta::{Metadata, MetadataOptions};
 use symphonia::core::probe::Hint;
+use symphonia::core::sample::SampleFormat;
+use symphonia::core::units::{Time, TimeBase};
+
+const MAX_CHECKPOINTS: usize = 4096;
+/// Filtered output is always interleaved stereo
+const OUTPUT_CHANNELS: usize = 2;
+const OUTPUT_TRACK_ID: u32 = 0;
+/// Largest packet handed to songbird, timescale can turn one source packet into many of these
+const MAX_PACKET_FRAMES: usize = 4096;
+/// Silence held back while checking whether it runs to the end of the track. Holding stalls the output,
+/// so longer runs are played unless they start within `TRAILING_SILENCE_SECS` of the known end
+const MAX_HELD_SILENCE_SECS: u64 = 2;
+const TRAILING_SILENCE_SECS: u64 = 10;
+/// Source audio one read may decode without producing output before it hands out a short silent packet,
+/// so skipping or holding silence never stalls songbird's mixer for long
+const MAX_DECODED_MS_PER_READ: u64 = 500;
+const SILENT_PACKET_MS: u64 = 20;
+
+/// Maps songbird's output timeline back to the source frames it was made from
+#[derive(Debug, Default)]
+pub struct SourcePosition {
+    inner: Mutex<PositionMap>,
+}
+
+#[derive(Debug, Default)]
+struct PositionMap {
+    sample_rate: u32,
+    /// Pairs of (output frame, source frame) recorded at the start of every filtered packet
+    checkpoints: VecDeque<(u64, u64)>,
+}
+
+impl SourcePosition {
+    /// Starts a new mapping, used when the source is created or seeked
+    fn restart(&self, sample_rate: u32, output_frame: u64, source_frame: u64) {
+        if let Ok(mut map) = self.inner.lock() {
+            map.sample_rate = sample_rate;
+            map.checkpoints.clear();
+            map.checkpoints.push_back((output_frame, source_frame));
+        }
+    }
+
+    fn record(&self, output_frame: u64, source_frame: u64) {
+        if let Ok(mut map) = self.inner.lock() {
+            if map.checkpoints.len() >= MAX_CHECKPOINTS {
+                map.checkpoints.pop_front();
+            }
+            map.checkpoints.push_back((output_frame, source_frame));
+        }
+    }
+
+    /// Stops mapping, used when the next track plays without a filtered source
+    pub fn clear(&self) {
+        if let Ok(mut map) = self.inner.lock() {
+            map.checkpoints.clear();
+        }
+    }
+
+    /// Converts an output position in ms into the matching source position, unchanged when nothing is mapped
+    pub fn to_source_ms(&self, output_ms: u64) -> u64 {
+        let Ok(map) = self.inner.lock() else {
+            return output_ms;
+        };
+
+        if map.sample_rate == 0 || map.checkpoints.is_empty() {
+            return output_ms;
+        }
 
-const WAV_HEADER_SIZE: usize = 44;
+        let rate = map.sample_rate as u64;
+        let output = output_ms * rate / 1000;
+
+        let index = map
+            .checkpoints
+            .partition_point(|(checkpoint, _)| *checkpoint <= output);
+
+        let source = match (
+            index.checked_sub(1).and_then(|i| map.checkpoints.get(i)),
+            map.checkpoints.get(index),
+        ) {
+            (Some(&(start_out, start_src)), Some(&(end_out, end_src))) if end_out > start_out => {
+                let progress = (output - start_out) as f64 / (end_out - start_out) as f64;
+                start_src + ((end_src.saturating_sub(start_src)) as f64 * progress) as u64
+            }
+            (Some(&(start_out, start_src)), _) => start_src + (output - start_out),
+            (None, Some(&(_, end_src))) => end_src,
+            (None, None) => output,
+        };
 
-pub struct FilteredSource {
+        source * 1000 / rate
+    }
+}
+
+/// Format reader that decodes the source, runs it through the filter chain and hands songbird
+/// raw f32 PCM packets, so songbird neither probes nor decodes the audio a second time
+pub struct FilteredFormat {
     format: Box<dyn FormatReader>,
-    decoder: Box<dyn symphonia::core::codecs::Decoder>,
+    decoder: Box<dyn Decoder>,
     track_id: u32,
+    time_base: Option<TimeBase>,
+    tracks: Vec<Track>,
     filter_chain: Arc<Mutex<FilterChain>>,
+    position: Arc<SourcePosition>,
 
-    pcm_buffer: Vec<u8>,
-    pcm_pos: usize,
+    pending: VecDeque<Packet>,
     current_pcm_frame: u64,
+    current_output_frame: u64,
+    /// Decoded frames still to drop after a seek landed before its target
+    skip_frames: u64,
+
+    /// Whether playback started at the beginning and has not reached audible audio yet
+    leading_silence: bool,
+    /// Silence that may be trailing, flushed once audio resumes and dropped at the end of the track
+    held_silence: Vec<f32>,
+    /// Source frame the held silence starts at
+    held_start: u64,
+    /// Held silent frames beyond what is kept in `held_silence`, played back as digital silence
+    held_excess: u64,
+    /// Source frame the track ends at, from the container or else the track length
+    end_frame: Option<u64>,
 
-    header: [u8; WAV_HEADER_SIZE],
-    header_pos: usize,
-    header_sent: bool,
-
-    _sample_rate: u32,
-    channels: usize,
-
-    seekable: bool,
+    sample_rate: u32,
 }
 
-impl FilteredSource {
-    pub fn new(
-        source: Box<dyn MediaSource>,
-        hint: Hint,
-        filter_chain: Arc<Mutex<FilterChain>>,
-        sample_rate: u32,
-        channels: usize,
-    ) -> Result<Self, io::Error> {
-        tracing::info!(
-            "FilteredSource::new ENTRY: sample_rate={}, channels={}, hint={:?}",
-            sample_rate,
-            channels,
-            hint
-        );
-
-        let seekable = source.is_seekable();
-        tracing::debug!("Creating MediaSourceStream, seekable={}", seekable);
-        let mss = MediaSourceStream::new(source, Default::default());
-        tracing::debug!("MediaSourceStream created, starting probe (with Opus support)...");
+impl FilteredFormat {
+    /// Probes a stream the way songbird would, picking the default or else the first decodable track
+    pub fn probe(mss: MediaSourceStream, hint: &Hint) -> Result<Parsed, io::Error> {
+        let supports_backseek = mss.is_seekable();
 
         let probed = get_probe()
             .format(
-                &hint,
+                hint,
                 mss,
                 &FormatOptions::default(),
                 &MetadataOptions::default(),
             )
-            .map_err(|e| {
-                tracing::error!("Probe FAILED: {e}");
-                io::Error::new(io::ErrorKind::Other, format!("Probe failed: {e}"))
-            })?;
+            .map_err(|e| io::Error::other(format!("Probe failed: {e}")))?;
+
+        let track = probed
+            .format
+            .default_track()
+            .filter(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+            .or_else(|| {
+                probed
+                    .format
+                    .tracks()
+                    .iter()
+                    .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+            })
+            .ok_or_else(|| io::Error::other("No supported audio track found"))?;
 
-        tracing::info!("Probe SUCCESS");
-        let format = probed.format;
+        let track_id = track.id;
+        let decoder = get_codec_registry()
+            .make(&track.codec_params, &DecoderOptions::default())
+            .map_err(|e| io::Error::other(format!("Decoder creation failed: {e}")))?;
+
+        Ok(Parsed {
+            format: probed.format,
+            decoder,
+            track_id,
+            meta: probed.metadata,
+            supports_backseek,
+        })
+    }
 
-        let track = format
+    /// Wraps a parsed input so its audio runs through the filter chain, the input is handed back untouched on failure.
+    /// `length_ms` is the track length, used to find the end when the container does not know it
+    pub fn wrap(
+        parsed: Parsed,
+        filter_chain: Arc<Mutex<FilterChain>>,
+        position: Arc<SourcePosition>,
+        length_ms: Option<u64>,
+    ) -> Result<Parsed, (Parsed, io::Error)> {
+        let track_params = parsed
+            .format
             .tracks()
             .iter()
-            .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
-            .ok_or_else(|| {
-                io::Error::new(io::ErrorKind::Other, "No supported audio track found")
-            })?;
-
-        let track_id = track.id;
-
-        let actual_sr = track.codec_params.sample_rate.unwrap_or(sample_rate);
-        let actual_ch = track
-            .codec_params
+            .find(|t| t.id == parsed.track_id)
+            .map(|t| &t.codec_params);
+        let decoder_params = parsed.decoder.codec_params();
+
+        let Some(sample_rate) = decoder_params
+            .sample_rate
+            .or_else(|| track_params.and_then(|p| p.sample_rate))
+        else {
+            return Err((parsed, io::Error::other("Source sample rate is unknown")));
+        };
+        let time_base = track_params
+            .and_then(|p| p.time_base)
+            .or(decoder_params.time_base);
+        let end_frame = track_params
+            .and_then(|p| p.n_frames)
+            .or(length_ms.map(|length| length * sample_rate as u64 / 1000));
+        let channels = decoder_params
             .channels
+            .or_else(|| track_params.and_then(|p| p.channels))
             .map(|c| c.count())
-            .unwrap_or(channels);
-
-        tracing::debug!(
-            "Creating decoder for track_id={}, codec={:?}",
-            track_id,
-            track.codec_params.codec
-        );
-        let decoder = get_codec_registry()
-            .make(&track.codec_params, &DecoderOptions::default())
-            .map_err(|e| {
-                tracing::error!("Decoder creation failed: {e}");
-                io::Error::new(
-                    io::ErrorKind::Other,
-                    format!("Decoder creation failed: {e}"),
-                )
-            })?;
-
-        tracing::info!(
-            "Decoder created successfully. Actual sample_rate={}, channels={}",
-            actual_sr,
-            actual_ch
-        );
+            .unwrap_or(OUTPUT_CHANNELS);
+
+        let mut params = CodecParameters::new();
+        params
+            .for_codec(CODEC_TYPE_PCM_F32LE)
+            .with_sample_rate(sample_rate)
+            .with_time_base(TimeBase::new(1, sample_rate))
+            .with_sample_format(SampleFormat::F32)
+            .with_bits_per_sample(32)
+            .with_bits_per_coded_sample(32)
+            .with_channels(Channels::FRONT_LEFT | Channels::FRONT_RIGHT)
+            .with_max_frames_per_packet(MAX_PACKET_FRAMES as u64);
+
+        let output_decoder = match get_codec_registry().make(&params, &DecoderOptions::default()) {
+            Ok(decoder) => decoder,
+            Err(e) => {
+                return Err((
+                    parsed,
+                    io::Error::other(format!("PCM decoder creation failed: {e}")),
+                ));
+            }
+        };
 
         if let Ok(mut chain) = filter_chain.lock() {
-            chain.set_sample_rate(actual_sr);
+            chain.set_sample_rate(sample_rate);
         }
 
-        let header = build_wav_header(actual_sr, actual_ch as u16);
+        position.restart(sample_rate, 0, 0);
 
-        tracing::info!(
-            "FilteredSource created successfully: {}Hz, {} channels, seekable={}",
-            actual_sr,
-            actual_ch,
-            seekable
+        tracing::debug!(
+            "FilteredFormat created: {}Hz, {} channels, seekable={}",
+            sample_rate,
+            channels,
+            parsed.supports_backseek
         );
 
-        Ok(Self {
-            format,
-            decoder,
-            track_id,
+        let filtered = Self {
+            format: parsed.format,
+            decoder: parsed.decoder,
+            track_id: parsed.track_id,
+            time_base,
+            tracks: vec![Track::new(OUTPUT_TRACK_ID, params)],
             filter_chain,
-            pcm_buffer: Vec::with_capacity(8192),
-            pcm_pos: 0,
+            position,
+            pending: VecDeque::new(),
             current_pcm_frame: 0,
-            header,
-            header_pos: 0,
-            header_sent: false,
-            _sample_rate: actual_sr,
-            channels: actual_ch,
-            seekable,
+            current_output_frame: 0,
+            skip_frames: 0,
+            leading_silence: true,
+            held_silence: Vec::new(),
+            held_start: 0,
+            held_excess: 0,
+            end_frame,
+            sample_rate,
+        };
+
+        Ok(Parsed {
+            format: Box::new(filtered),
+            decoder: output_decoder,
+            track_id: OUTPUT_TRACK_ID,
+            meta: parsed.meta,
+            supports_backseek: parsed.supports_backseek,
         })
     }
+
+    fn frames_to_time(&self, frames: u64) -> Time {
+        TimeBase::new(1, self.sample_rate).calc_time(frames)
+    }
+
+    fn time_to_frames(&self, time: Time) -> u64 {
+        TimeBase::new(1, self.sample_rate).calc_timestamp(time)
+    }
+
+    /// Interleaved stereo out of any channel layout, mono is duplicated and extra channels dropped
+    fn to_stereo(samples: &[f32], channels: usize) -> Vec<f32> {
+        match channels {
+            2 => samples.to_vec(),
+            1 => samples.iter().flat_map(|&s| [s, s]).collect(),
+            _ => samples
+                .chunks_exact(channels)
+                .flat_map(|frame| [frame[0], frame[1]])
+                .collect(),
+        }
+    }
+
+    /// Filters `samples`, which start at source frame `position`, and queues the output
+    fn emit(&mut self, mut samples: Vec<f32>, position: u64) {
+        if let Ok(mut chain) = self.filter_chain.lock() {
+            let _ = chain.process(&mut samples, position);
+        }
+
+        // timescale may hold everything back until it has a full sequence buffered
+        if samples.is_empty() {
+            return;
+        }
+
+        self.position.record(self.current_output_frame, position);
+        self.queue_packets(&samples);
+    }
+
+    /// Queues what the filters still hold once the source has ended, false when nothing was left
+    fn drain_filters(&mut self) -> bool {
+        let samples = match self.filter_chain.lock() {
+            Ok(mut chain) => chain.flush(self.current_pcm_frame).unwrap_or_default(),
+            Err(_) => return false,
+        };
+
+        if samples.is_empty() {
+            return false;
+        }
+
+        self.position
+            .record(self.current_output_frame, self.current_pcm_frame);
+        self.queue_packets(&samples);
+        true
+    }
+
+    /// Plays the held silence, it turned out not to be trailing
+    fn flush_silence(&mut self) {
+        let held = std::mem::take(&mut self.held_silence);
+        let mut position = self.held_start + (held.len() / OUTPUT_CHANNELS) as u64;
+        if !held.is_empty() {
+            self.emit(held, self.held_start);
+        }
+
+        while self.held_excess > 0 {
+            let frames = self.held_excess.min(MAX_PACKET_FRAMES as u64);
+            self.emit(vec![0.0; frames as usize * OUTPUT_CHANNELS], position);
+            self.held_excess -= frames;
+            position += frames;
+        }
+    }
+
+    fn hold_silence(&mut self, samples: &[f32], position: u64) {
+        if self.held_silence.is_empty() && self.held_excess == 0 {
+            self.held_start = position;
+        }
+
+        let capacity = (MAX_HELD_SILENCE_SECS * self.sample_rate as u64) as usize * OUTPUT_CHANNELS;
+        let kept = samples
+            .len()
+            .min(capacity.saturating_sub(self.held_silence.len()));
+        self.held_silence.extend_from_slice(&samples[..kept]);
+        self.held_excess += ((samples.len() - kept) / OUTPUT_CHANNELS) as u64;
+
+        // only silence close to the known end is worth holding past the limit
+        let trailing = self.end_frame.is_some_and(|end| {
+            end.saturating_sub(self.held_start) <= TRAILING_SILENCE_SECS * self.sample_rate as u64
+        });
+        if self.held_excess > 0 && !trailing {
+            self.flush_silence();
+        }
+    }
+
+    /// Cuts silence before the first audible frame and holds back silence that may run to the end
+    fn trim_silence(&mut self, samples: Vec<f32>, position: u64, threshold: f32) {
+        let audible = |frame: &[f32]| frame[0].abs() > threshold || frame[1].abs() > threshold;
+        let first = samples.chunks_exact(OUTPUT_CHANNELS).position(audible);
+        let last = samples.chunks_exact(OUTPUT_CHANNELS).rposition(audible);
+
+        let (Some(first), Some(last)) = (first, last) else {
+            if !self.leading_silence {
+                self.hold_silence(&samples, position);
+            }
+            return;
+        };
+
+        let start = if self.leading_silence {
+            self.leading_silence = false;
+            first
+        } else {
+            self.flush_silence();
+            0
+        };
+
+        let end = last + 1;
+        self.hold_silence(&samples[end * OUTPUT_CHANNELS..], position + end as u64);
+        self.emit(
+            samples[start * OUTPUT_CHANNELS..end * OUTPUT_CHANNELS].to_vec(),
+            position + start as u64,
+        );
+    }
+
+    fn queue_packets(&mut self, samples: &[f32]) {
+        for chunk in samples.chunks(MAX_PACKET_FRAMES * OUTPUT_CHANNELS) {
+            let frames = (chunk.len() / OUTPUT_CHANNELS) as u64;
+            let data: Vec<u8> = chunk.iter().flat_map(|s| s.to_le_bytes()).collect();
+
+            self.pending.push_back(Packet::new_from_boxed_slice(
+                OUTPUT_TRACK_ID,
+                self.current_output_frame,
+                frames,
+                data.into_boxed_slice(),
+            ));
+
+            self.current_output_frame += frames;
+        }
+    }
 }
 
-impl Read for FilteredSource {
-    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        if !self.header_sent {
-            let avail = WAV_HEADER_SIZE - self.header_pos;
-            if avail > 0 {
-                let n = buf.len().min(avail);
-                buf[..n].copy_from_slice(&self.header[self.header_pos..self.header_pos + n]);
-                self.header_pos += n;
-                if self.header_pos >= WAV_HEADER_SIZE {
-                    self.header_sent = true;
-                }
-                return Ok(n);
+impl FormatReader for FilteredFormat {
+    fn try_new(_: MediaSourceStream, _: &FormatOptions) -> SymphoniaResult<Self> {
+        Err(SymphoniaError::Unsupported(
+            "FilteredFormat needs a filter chain and is built through FilteredFormat::wrap",
+        ))
+    }
+
+    fn cues(&self) -> &[Cue] {
+        self.format.cues()
+    }
+
+    fn metadata(&mut self) -> Metadata<'_> {
+        self.format.metadata()
+    }
+
+    fn seek(&mut self, mode: SeekMode, to: SeekTo) -> SymphoniaResult<SeekedTo> {
+        // songbird keeps counting from the seek target, so the target is both source and output time
+        let target = match to {
+            SeekTo::Time { time, .. } => self.time_to_frames(time),
+            SeekTo::TimeStamp { ts, .. } => ts,
+        };
+
+        let seeked = self.format.seek(
+            mode,
+            SeekTo::Time {
+                time: self.frames_to_time(target),
+                track_id: Some(self.track_id),
+            },
+        );
+
+        match seeked {
+            Ok(seeked) => {
+                let actual = match self.time_base {
+                    Some(time_base) => self.time_to_frames(time_base.calc_time(seeked.actual_ts)),
+                    None => seeked.actual_ts,
+                };
+                self.current_pcm_frame = actual.min(target);
+                self.skip_frames = target.saturating_sub(actual);
             }
-            self.header_sent = true;
+            Err(e) if target >= self.current_pcm_frame => {
+                tracing::debug!(
+                    "Inner format seek failed ({}). Resolving via manual packet discard to frame {}",
+                    e,
+                    target
+                );
+                self.skip_frames = target - self.current_pcm_frame;
+            }
+            Err(e) => return Err(e),
         }
 
+        self.decoder.reset();
+        self.pending.clear();
+        self.leading_silence = false;
+        self.held_silence.clear();
+        self.held_excess = 0;
+        self.current_output_frame = target;
+        self.position.restart(self.sample_rate, target, target);
+
+        if let Ok(mut chain) = self.filter_chain.lock() {
+            chain.reset_state();
+        }
+
+        Ok(SeekedTo {
+            track_id: OUTPUT_TRACK_ID,
+            required_ts: target,
+            actual_ts: target,
+        })
+    }
+
+    fn tracks(&self) -> &[Track] {
+        &self.tracks
+    }
+
+    fn next_packet(&mut self) -> SymphoniaResult<Packet> {
+        let budget = MAX_DECODED_MS_PER_READ * self.sample_rate as u64 / 1000;
+        let mut decoded_frames = 0;
+
         loop {
-            if self.pcm_pos < self.pcm_buffer.len() {
-                let avail = self.pcm_buffer.len() - self.pcm_pos;
-                let n = buf.len().min(avail);
-                buf[..n].copy_from_slice(&self.pcm_buffer[self.pcm_pos..self.pcm_pos + n]);
-                self.pcm_pos += n;
-                return Ok(n);
+            if let Some(packet) = self.pending.pop_front() {
+                return Ok(packet);
             }
 
-            self.pcm_buffer.clear();
-            self.pcm_pos = 0;
+            if decoded_frames >= budget {
+                let frames = (SILENT_PACKET_MS * self.sample_rate as u64 / 1000).max(1);
+                self.position
+                    .record(self.current_output_frame, self.current_pcm_frame);
+                self.queue_packets(&vec![0.0; frames as usize * OUTPUT_CHANNELS]);
+                continue;
+            }
 
             let packet = match self.format.next_packet() {
-                Ok(p) => p,
-                Err(symphonia::core::errors::Error::IoError(ref e))
-                    if e.kind() == io::ErrorKind::UnexpectedEof =>
-                {
-                    return Ok(0);
-                }
-                Err(symphonia::core::errors::Error::ResetRequired) => {
+                Ok(packet) => packet,
+                Err(SymphoniaError::ResetRequired) => {
                     self.decoder.reset();
                     continue;
                 }
-                Err(e) => {
-                    return Err(io::Error::new(
-                        io::ErrorKind::Other,
-                        format!("Format read error: {e}"),
-                    ));
+                // timescale still holds the last few ms of the track when the source runs out
+                Err(SymphoniaError::IoError(e))
+                    if e.kind() == io::ErrorKind::UnexpectedEof && self.drain_filters() =>
+                {
+                    continue;
                 }
+                Err(e) => return Err(e),
             };
 
             if packet.track_id() != self.track_id {
@@ -189,260 +521,117 @@ impl Read for FilteredSource {
             }
 
             let decoded = match self.decoder.decode(&packet) {
-                Ok(d) => d,
-                Err(symphonia::core::errors::Error::DecodeError(_)) => continue,
-                Err(e) => {
-                    return Err(io::Error::new(
-                        io::ErrorKind::Other,
-                        format!("Decode error: {e}"),
-                    ));
-                }
+                Ok(decoded) => decoded,
+                Err(SymphoniaError::DecodeError(_)) => continue,
+                Err(e) => return Err(e),
             };
 
             let spec = *decoded.spec();
-            let frames = decoded.frames();
+            let frames = decoded.frames() as u64;
             if frames == 0 {
                 continue;
             }
-            self.current_pcm_frame += frames as u64;
-
-            let mut sample_buf = SampleBuffer::<i16>::new(frames as u64, spec);
-            sample_buf.copy_interleaved_ref(decoded);
-            let mut samples: Vec<i16> = sample_buf.samples().to_vec();
 
-            if let Ok(mut chain) = self.filter_chain.lock() {
-                let _ = chain.process(&mut samples);
-            }
+            let skip = self.skip_frames.min(frames);
+            self.skip_frames -= skip;
+            // discarding up to a seek target has to finish before anything plays
+            decoded_frames += frames - skip;
+            let position = self.current_pcm_frame + skip;
+            self.current_pcm_frame += frames;
 
-            self.pcm_buffer.reserve(samples.len() * 2);
-            for &s in &samples {
-                self.pcm_buffer.extend_from_slice(&s.to_le_bytes());
+            if skip == frames {
+                continue;
             }
-        }
-    }
-}
 
-impl Seek for FilteredSource {
-    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
-        match pos {
-            SeekFrom::Start(byte_pos) => {
-                if byte_pos < WAV_HEADER_SIZE as u64 {
-                    if !self.seekable {
-                        return Err(io::Error::new(
-                            io::ErrorKind::Unsupported,
-                            "Source is not seekable backwards to WAV header",
-                        ));
-                    }
-
-                    if let Err(e) = self.format.seek(
-                        symphonia::core::formats::SeekMode::Accurate,
-                        symphonia::core::formats::SeekTo::TimeStamp {
-                            ts: 0,
-                            track_id: self.track_id,
-                        },
-                    ) {
-                        return Err(io::Error::new(io::ErrorKind::InvalidInput, e.to_string()));
-                    }
-
-                    self.header_sent = false;
-                    self.header_pos = byte_pos as usize;
-                    self.pcm_buffer.clear();
-                    self.pcm_pos = 0;
-                    self.decoder.reset();
-                    if let Ok(mut chain) = self.filter_chain.lock() {
-                        chain.reset_state();
-                    }
-                    self.current_pcm_frame = 0;
-
-                    return Ok(byte_pos);
-                }
-
-                let pcm_bytes = byte_pos - WAV_HEADER_SIZE as u64;
-                let bytes_per_frame = 2 * self.channels as u64;
-                let frame_offset = pcm_bytes / bytes_per_frame;
-
-                if !self.seekable && frame_offset < self.current_pcm_frame {
-                    return Err(io::Error::new(
-                        io::ErrorKind::Unsupported,
-                        "Source is not seekable backwards",
-                    ));
-                }
-
-                match self.format.seek(
-                    symphonia::core::formats::SeekMode::Accurate,
-                    symphonia::core::formats::SeekTo::TimeStamp {
-                        ts: frame_offset,
-                        track_id: self.track_id,
-                    },
-                ) {
-                    Ok(_) => {
-                        self.decoder.reset();
-                        self.pcm_buffer.clear();
-                        self.pcm_pos = 0;
-                        self.header_sent = true;
-                        self.current_pcm_frame = frame_offset;
-                        if let Ok(mut chain) = self.filter_chain.lock() {
-                            chain.reset_state();
-                        }
-                        Ok(byte_pos)
-                    }
-                    Err(e) => {
-                        tracing::debug!(
-                            "Inner format seek failed ({}). Resolving via manual packet discard to frame {}",
-                            e,
-                            frame_offset
-                        );
-
-                        if self
-                            .format
-                            .seek(
-                                symphonia::core::formats::SeekMode::Accurate,
-                                symphonia::core::formats::SeekTo::TimeStamp {
-                                    ts: 0,
-                                    track_id: self.track_id,
-                                },
-                            )
-                            .is_ok()
-                        {
-                            self.current_pcm_frame = 0;
-                        }
-
-                        self.decoder.reset();
-                        self.pcm_buffer.clear();
-                        self.pcm_pos = 0;
-                        self.header_sent = true;
-
-                        loop {
-                            let packet = match self.format.next_packet() {
-                                Ok(p) => p,
-                                Err(symphonia::core::errors::Error::ResetRequired) => {
-                                    self.decoder.reset();
-                                    continue;
-                                }
-                                Err(_) => break, // EOF or fatal read error, stop skipping
-                            };
-
-                            if packet.track_id() != self.track_id {
-                                continue;
-                            }
-
-                            let decoded = match self.decoder.decode(&packet) {
-                                Ok(d) => d,
-                                Err(symphonia::core::errors::Error::DecodeError(_)) => continue,
-                                Err(_) => continue,
-                            };
-
-                            self.current_pcm_frame += decoded.frames() as u64;
-                            if self.current_pcm_frame >= frame_offset {
-                                break;
-                            }
-                        }
-
-                        if let Ok(mut chain) = self.filter_chain.lock() {
-                            chain.reset_state();
-                        }
-                        Ok(byte_pos)
-                    }
+            let mut sample_buf = SampleBuffer::<f32>::new(frames, spec);
+            sample_buf.copy_interleaved_ref(decoded);
+            let channels = spec.channels.count().max(1);
+            let kept = &sample_buf.samples()[skip as usize * channels..];
+            let samples = Self::to_stereo(kept, channels);
+
+            let threshold = self
+                .filter_chain
+                .lock()
+                .ok()
+                .and_then(|chain| chain.silence_threshold());
+
+            match threshold {
+                Some(threshold) => self.trim_silence(samples, position, threshold),
+                None => {
+                    self.leading_silence = false;
+                    self.flush_silence();
+                    self.emit(samples, position);
                 }
             }
-            _ => Err(io::Error::new(
-                io::ErrorKind::Unsupported,
-                "Only SeekFrom::Start is supported",
-            )),
         }
     }
-}
-
-impl MediaSource for FilteredSource {
-    fn is_seekable(&self) -> bool {
-        true
-    }
 
-    fn byte_len(&self) -> Option<u64> {
-        None
+    fn into_inner(self: Box<Self>) -> MediaSourceStream {
+        self.format.into_inner()
     }
 }
 
-fn build_wav_header(sample_rate: u32, num_channels: u16) -> [u8; WAV_HEADER_SIZE] {
-    let bits_per_sample: u16 = 16;
-    let block_align = num_channels * (bits_per_sample / 8);
-    let byte_rate = sample_rate * block_align as u32;
-    let data_size: u32 = 0x7FFF_FF00;
-
-    let mut h = [0u8; WAV_HEADER_SIZE];
-    h[0..4].copy_from_slice(b"RIFF");
-    h[4..8].copy_from_slice(&(36 + data_size).to_le_bytes());
-    h[8..12].copy_from_slice(b"WAVE");
-    h[12..16].copy_from_slice(b"fmt ");
-    h[16..20].copy_from_slice(&16u32.to_le_bytes());
-    h[20..22].copy_from_slice(&1u16.to_le_bytes());
-    h[22..24].copy_from_slice(&num_channels.to_le_bytes());
-    h[24..28].copy_from_slice(&sample_rate.to_le_bytes());
-    h[28..32].copy_from_slice(&byte_rate.to_le_bytes());
-    h[32..34].copy_from_slice(&block_align.to_le_bytes());
-    h[34..36].copy_from_slice(&bits_per_sample.to_le_bytes());
-    h[36..40].copy_from_slice(b"data");
-    h[40..44].copy_from_slice(&data_size.to_le_bytes());
-    h
-}
-
+/// Compose that runs every stream it recreates through the filter chain. Songbird recreates the input
+/// this way for backward seeks on sources that cannot seek back, which would otherwise play unfiltered
 pub struct FilteredCompose {
     inner: Box<dyn Compose>,
     filter_chain: Arc<Mutex<FilterChain>>,
-    sample_rate: u32,
-    channels: usize,
+    position: Arc<SourcePosition>,
+    length_ms: Option<u64>,
 }
 
 impl FilteredCompose {
     pub fn new(
         inner: Box<dyn Compose>,
         filter_chain: Arc<Mutex<FilterChain>>,
-        sample_rate: u32,
-        channels: usize,
+        position: Arc<SourcePosition>,
+        length_ms: Option<u64>,
     ) -> Self {
         Self {
             inner,
             filter_chain,
-            sample_rate,
-            channels,
+            position,
+            length_ms,
         }
     }
 
-    fn build_filtered_blocking(
+    /// Probes the recreated stream, wraps it in a FilteredFormat and hands it back as raw PCM for songbird
+    fn filter(
+        &self,
         stream: AudioStream<Box<dyn MediaSource>>,
-        filter_chain: Arc<Mutex<FilterChain>>,
-        sample_rate: u32,
-        channels: usize,
     ) -> Result<AudioStream<Box<dyn MediaSource>>, AudioStreamError> {
-        tracing::debug!(
-            "FilteredCompose::build_filtered_blocking called, hint={:?}",
-            stream.hint
-        );
+        let parsed = FilteredFormat::probe(
+            MediaSourceStream::new(stream.input, Default::default()),
+            &stream.hint.unwrap_or_default(),
+        )
+        .map_err(|e| AudioStreamError::Fail(Box::new(e)))?;
 
-        let hint = stream.hint.unwrap_or_default();
-        let filtered = FilteredSource::new(stream.input, hint, filter_chain, sample_rate, channels)
-            .map_err(|e| {
-                let err_msg = format!("{e}");
-                if err_msg.contains("unsupported codec") {
-                    tracing::warn!(
-                        "Unsupported codec detected (likely Opus/WebM). Filters cannot be applied. \
-                     Consider using JioSaavn or other AAC/MP3 sources for filter support."
-                    );
-                } else {
-                    tracing::error!("FilteredSource::new failed in build_filtered_blocking: {e}");
-                }
-                AudioStreamError::Fail(Box::new(e))
-            })?;
+        let filtered = FilteredFormat::wrap(
+            parsed,
+            self.filter_chain.clone(),
+            self.position.clone(),
+            self.length_ms,
+        )
+        .map_err(|(_, e)| AudioStreamError::Fail(Box::new(e)))?;
 
-        tracing::debug!("FilteredCompose::build_filtered_blocking succeeded, returning WAV stream");
+        let sample_rate = filtered
+            .format
+            .tracks()
+            .first()
+            .and_then(|track| track.codec_params.sample_rate)
+            .unwrap_or_default();
+
+        let pcm = FilteredStream {
+            format: filtered.format,
+            buffer: Vec::new(),
+            offset: 0,
+            position: 0,
+            seekable: filtered.supports_backseek,
+        };
 
         Ok(AudioStream {
-            input: Box::new(filtered) as Box<dyn MediaSource>,
-            hint: Some({
-                let mut h = Hint::new();
-                h.with_extension("wav");
-                h
-            }),
+            input: Box::new(RawAdapter::new(pcm, sample_rate, OUTPUT_CHANNELS as u32)),
+            hint: None,
         })
     }
 }
@@ -450,45 +639,106 @@ impl FilteredCompose {
 #[async_trait]
 impl Compose for FilteredCompose {
     fn create(&mut self) -> Result<AudioStream<Box<dyn MediaSource>>, AudioStreamError> {
-        tracing::debug!("FilteredCompose::create (sync) called");
         let stream = self.inner.create()?;
-        Self::build_filtered_blocking(
-            stream,
-            self.filter_chain.clone(),
-            self.sample_rate,
-            self.channels,
-        )
+        self.filter(stream)
     }
 
     async fn create_async(
         &mut self,
     ) -> Result<AudioStream<Box<dyn MediaSource>>, AudioStreamError> {
-        tracing::debug!("FilteredCompose::create_async called, resolving inner compose...");
-        let stream = self.inner.create_async().await.map_err(|e| {
-            tracing::error!("Inner compose create_async failed: {:?}", e);
-            e
-        })?;
-        tracing::debug!(
-            "Inner compose resolved successfully, spawning blocking task for symphonia probe..."
-        );
-
-        let filter_chain = self.filter_chain.clone();
-        let sample_rate = self.sample_rate;
-        let channels = self.channels;
-
-        tokio::task::spawn_blocking(move || {
-            Self::build_filtered_blocking(stream, filter_chain, sample_rate, channels)
-        })
-        .await
-        .map_err(|e| {
-            AudioStreamError::Fail(Box::new(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!("Blocking task failed: {e}"),
-            )))
-        })?
+        let stream = self.inner.create_async().await?;
+        tokio::task::block_in_place(|| self.filter(stream))
     }
 
     fn should_create_async(&self) -> bool {
         self.inner.should_create_async()
     }
+
+    async fn aux_metadata(&mut self) -> Result<AuxMetadata, AudioStreamError> {
+        self.inner.aux_metadata().await
+    }
+}
+
+/// Filtered output as a byte stream of interleaved f32 PCM, byte offsets map straight to output frames
+struct FilteredStream {
+    format: Box<dyn FormatReader>,
+    buffer: Vec<u8>,
+    offset: usize,
+    position: u64,
+    seekable: bool,
+}
+
+impl FilteredStream {
+    const FRAME_BYTES: u64 = (OUTPUT_CHANNELS * size_of::<f32>()) as u64;
+}
+
+impl Read for FilteredStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.offset >= self.buffer.len() {
+            match self.format.next_packet() {
+                Ok(packet) => {
+                    self.buffer = packet.data.into_vec();
+                    self.offset = 0;
+                }
+                Err(SymphoniaError::IoError(e)) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                    return Ok(0);
+                }
+                Err(SymphoniaError::IoError(e)) => return Err(e),
+                Err(e) => return Err(io::Error::other(e)),
+            }
+        }
+
+        let read = buf.len().min(self.buffer.len() - self.offset);
+        buf[..read].copy_from_slice(&self.buffer[self.offset..self.offset + read]);
+        self.offset += read;
+        self.position += read as u64;
+
+        Ok(read)
+    }
+}
+
+impl Seek for FilteredStream {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(offset) => offset,
+            SeekFrom::Current(offset) => self.position.saturating_add_signed(offset),
+            SeekFrom::End(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "Filtered stream length is unknown",
+                ));
+            }
+        };
+
+        if target == self.position {
+            return Ok(target);
+        }
+
+        let frame = target / Self::FRAME_BYTES;
+        self.format
+            .seek(
+                SeekMode::Accurate,
+                SeekTo::TimeStamp {
+                    ts: frame,
+                    track_id: OUTPUT_TRACK_ID,
+                },
+            )
+            .map_err(io::Error::other)?;
+
+        self.buffer.clear();
+        self.offset = 0;
+        self.position = frame * Self::FRAME_BYTES;
+
+        Ok(self.position)
+    }
+}
+
+impl MediaSource for FilteredStream {
+    fn is_seekable(&self) -> bool {
+        self.seekable
+    }
+
+    fn byte_len(&self) -> Option<u64> {
+        None
+    }
 }
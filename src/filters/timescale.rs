@@ -1,10 +1,279 @@
 use super::{AudioFilter, FilterError};
+use std::f64::consts::PI;
+
+const CHANNELS: usize = 2;
+
+/// WSOLA sequence, seek window and overlap lengths in milliseconds
+const SEQUENCE_MS: u32 = 40;
+const SEEK_WINDOW_MS: u32 = 15;
+const OVERLAP_MS: u32 = 8;
+
+/// Half the number of sinc taps used by the resampler, and the kernel table resolution
+const RESAMPLER_HALF_TAPS: usize = 16;
+const RESAMPLER_OVERSAMPLE: usize = 128;
+
+/// Changes tempo without touching pitch using waveform similarity overlap-add
+struct Stretcher {
+    tempo: f64,
+    sample_rate: u32,
+    sequence: usize,
+    seek_window: usize,
+    overlap: usize,
+    input: Vec<f32>,
+    overlap_buffer: Vec<f32>,
+    overlap_mono: Vec<f32>,
+    skip_fract: f64,
+    primed: bool,
+}
+
+impl Stretcher {
+    fn new(tempo: f64) -> Self {
+        Self {
+            tempo,
+            sample_rate: 0,
+            sequence: 0,
+            seek_window: 0,
+            overlap: 0,
+            input: Vec::new(),
+            overlap_buffer: Vec::new(),
+            overlap_mono: Vec::new(),
+            skip_fract: 0.0,
+            primed: false,
+        }
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: u32) {
+        if self.sample_rate == sample_rate {
+            return;
+        }
+
+        let frames = |ms: u32| (sample_rate as usize * ms as usize / 1000).max(1);
+
+        self.sample_rate = sample_rate;
+        self.sequence = frames(SEQUENCE_MS);
+        self.seek_window = frames(SEEK_WINDOW_MS);
+        self.overlap = frames(OVERLAP_MS).min(self.sequence / 2);
+        self.reset();
+    }
+
+    fn reset(&mut self) {
+        self.input.clear();
+        self.overlap_buffer.clear();
+        self.overlap_mono.clear();
+        self.skip_fract = 0.0;
+        self.primed = false;
+    }
+
+    fn process(&mut self, input: &[f32], output: &mut Vec<f32>) {
+        self.input.extend_from_slice(input);
+
+        let nominal_skip = self.tempo * (self.sequence - self.overlap) as f64;
+        let required =
+            (nominal_skip.ceil() as usize + self.overlap).max(self.seek_window + self.sequence);
+
+        while self.input.len() / CHANNELS >= required {
+            let offset = if self.primed { self.best_offset() } else { 0 };
+            let overlap_start = offset * CHANNELS;
+            let body_start = (offset + self.overlap) * CHANNELS;
+            let body_end = (offset + self.sequence - self.overlap) * CHANNELS;
+            let sequence_end = (offset + self.sequence) * CHANNELS;
+
+            if self.primed {
+                let length = self.overlap as f32;
+                for frame in 0..self.overlap {
+                    let fade_in = frame as f32 / length;
+                    let fade_out = 1.0 - fade_in;
+                    for channel in 0..CHANNELS {
+                        let index = frame * CHANNELS + channel;
+                        output.push(
+                            self.overlap_buffer[index] * fade_out
+                                + self.input[overlap_start + index] * fade_in,
+                        );
+                    }
+                }
+            } else {
+                output.extend_from_slice(&self.input[overlap_start..body_start]);
+            }
+
+            output.extend_from_slice(&self.input[body_start..body_end]);
+
+            self.overlap_buffer.clear();
+            self.overlap_buffer
+                .extend_from_slice(&self.input[body_end..sequence_end]);
+            self.overlap_mono.clear();
+            self.overlap_mono.extend(
+                self.overlap_buffer
+                    .chunks_exact(CHANNELS)
+                    .map(|frame| frame.iter().sum::<f32>()),
+            );
+            self.primed = true;
+
+            self.skip_fract += nominal_skip;
+            let skip = self.skip_fract.floor() as usize;
+            self.skip_fract -= skip as f64;
+            self.input.drain(..skip * CHANNELS);
+        }
+    }
+
+    /// Emits what is left of the input at the current tempo, crossfaded into the last overlap
+    fn flush(&mut self, output: &mut Vec<f32>) {
+        let mut start = 0;
+
+        if self.primed {
+            let frames = self.overlap.min(self.input.len() / CHANNELS);
+            let length = self.overlap as f32;
+            for frame in 0..self.overlap {
+                let fade_in = frame as f32 / length;
+                for channel in 0..CHANNELS {
+                    let index = frame * CHANNELS + channel;
+                    let sample = if frame < frames {
+                        self.input[index] * fade_in
+                    } else {
+                        0.0
+                    };
+                    output.push(self.overlap_buffer[index] * (1.0 - fade_in) + sample);
+                }
+            }
+            start = frames * CHANNELS;
+        }
+
+        output.extend_from_slice(&self.input[start..]);
+        self.reset();
+    }
+
+    /// Offset in the seek window whose start best continues the previous sequence
+    fn best_offset(&self) -> usize {
+        let mut best_offset = 0;
+        let mut best_score = f64::MIN;
+
+        for offset in 0..self.seek_window {
+            let mut correlation = 0.0f64;
+            let mut energy = 0.0f64;
+
+            for (frame, reference) in self.overlap_mono.iter().enumerate() {
+                let start = (offset + frame) * CHANNELS;
+                let sample: f32 = self.input[start..start + CHANNELS].iter().sum();
+                correlation += (*reference * sample) as f64;
+                energy += (sample * sample) as f64;
+            }
+
+            let score = correlation / energy.max(1e-9).sqrt();
+            if score > best_score {
+                best_score = score;
+                best_offset = offset;
+            }
+        }
+
+        best_offset
+    }
+}
+
+/// Band-limited windowed sinc resampler, a ratio above 1.0 reads the input faster
+struct Resampler {
+    ratio: f64,
+    kernel: Vec<f32>,
+    buffer: Vec<f32>,
+    position: f64,
+}
+
+impl Resampler {
+    fn new(ratio: f64) -> Self {
+        let cutoff = (1.0 / ratio).min(1.0);
+        let half_taps = RESAMPLER_HALF_TAPS as f64;
+
+        let kernel = (0..=RESAMPLER_HALF_TAPS * RESAMPLER_OVERSAMPLE)
+            .map(|index| {
+                let x = index as f64 / RESAMPLER_OVERSAMPLE as f64;
+                let sinc = if index == 0 {
+                    1.0
+                } else {
+                    (PI * cutoff * x).sin() / (PI * cutoff * x)
+                };
+                let window = 0.42
+                    + 0.5 * (PI * x / half_taps).cos()
+                    + 0.08 * (2.0 * PI * x / half_taps).cos();
+                (cutoff * sinc * window) as f32
+            })
+            .collect();
+
+        let mut resampler = Self {
+            ratio,
+            kernel,
+            buffer: Vec::new(),
+            position: 0.0,
+        };
+        resampler.reset();
+        resampler
+    }
+
+    fn reset(&mut self) {
+        self.buffer.clear();
+        self.buffer.resize(RESAMPLER_HALF_TAPS * CHANNELS, 0.0);
+        self.position = RESAMPLER_HALF_TAPS as f64;
+    }
+
+    fn weight(&self, distance: f64) -> f32 {
+        let scaled = distance * RESAMPLER_OVERSAMPLE as f64;
+        let index = scaled.floor() as usize;
+        if index + 1 >= self.kernel.len() {
+            return 0.0;
+        }
+        let frac = (scaled - index as f64) as f32;
+        self.kernel[index] * (1.0 - frac) + self.kernel[index + 1] * frac
+    }
+
+    fn process(&mut self, input: &[f32], output: &mut Vec<f32>) {
+        self.buffer.extend_from_slice(input);
+        let frames = self.buffer.len() / CHANNELS;
+
+        while self.position.floor() as usize + RESAMPLER_HALF_TAPS < frames {
+            output.extend_from_slice(&self.interpolate());
+            self.position += self.ratio;
+        }
+
+        let consumed = (self.position.floor() as usize)
+            .saturating_sub(RESAMPLER_HALF_TAPS)
+            .min(frames);
+        self.buffer.drain(..consumed * CHANNELS);
+        self.position -= consumed as f64;
+    }
+
+    /// Emits the frames still waiting on look-ahead, padding the end of the input with silence
+    fn flush(&mut self, output: &mut Vec<f32>) {
+        let end = (self.buffer.len() / CHANNELS) as f64;
+        self.buffer
+            .resize(self.buffer.len() + RESAMPLER_HALF_TAPS * CHANNELS, 0.0);
+
+        while self.position < end {
+            output.extend_from_slice(&self.interpolate());
+            self.position += self.ratio;
+        }
+
+        self.reset();
+    }
+
+    /// Output frame at the current position, the buffer must hold the taps on both sides of it
+    fn interpolate(&self) -> [f32; CHANNELS] {
+        let base = self.position.floor() as usize;
+        let mut acc = [0.0f32; CHANNELS];
+
+        for frame in (base + 1 - RESAMPLER_HALF_TAPS)..=(base + RESAMPLER_HALF_TAPS) {
+            let weight = self.weight((frame as f64 - self.position).abs());
+            for (channel, value) in acc.iter_mut().enumerate() {
+                *value += self.buffer[frame * CHANNELS + channel] * weight;
+            }
+        }
+
+        acc
+    }
+}
 
 pub struct TimescaleFilter {
     speed: f64,
     pitch: f64,
     rate: f64,
-    accumulator: f64,
+    stretcher: Stretcher,
+    resampler: Resampler,
 }
 
 impl TimescaleFilter {
@@ -32,41 +301,75 @@ impl TimescaleFilter {
             speed,
             pitch,
             rate,
-            accumulator: 0.0,
+            stretcher: Stretcher::new(speed / pitch),
+            resampler: Resampler::new(pitch * rate),
         })
     }
 
-    fn effective_rate(&self) -> f64 {
+    /// Source frames consumed per output frame
+    pub fn playback_rate(&self) -> f64 {
         self.speed * self.rate
     }
+
+    /// Tempo change done by the stretcher, the resampler's tempo change is compensated here
+    fn tempo(&self) -> f64 {
+        self.speed / self.pitch
+    }
+
+    /// Resampling ratio, which shifts pitch and tempo together
+    fn resample_ratio(&self) -> f64 {
+        self.pitch * self.rate
+    }
 }
 
 impl AudioFilter for TimescaleFilter {
-    fn process(&mut self, _samples: &mut [i16], _sample_rate: u32) -> Result<(), FilterError> {
-        // For the basic implementation, we only adjust speed by
-        // resampling (nearest-neighbour) the buffer in-place.
-        //
-        // Pitch adjustment without speed change requires WSOLA or
-        // phase-vocoder, which is deferred to a future iteration.
-        //
-        // When speed != 1.0 and pitch == 1.0, Lavalink effectively
-        // resamples the audio, which is what we approximate here.
-
-        let rate = self.effective_rate();
-        if (rate - 1.0).abs() < f64::EPSILON && (self.pitch - 1.0).abs() < f64::EPSILON {
-            return Ok(()); // Nothing to do
-        }
-
-        // NOTE: True timescale processing would modify the buffer length,
-        // which is incompatible with the in-place `&mut [i16]` API.
-        // Full implementation requires the FilterChain to manage buffer
-        // resizing. For now, we apply a simple pitch-shift-via-resampling
-        // approach in-place (which also changes speed — acceptable for
-        // the basic implementation).
-        //
-        // This is a known limitation; a complete implementation would use
-        // WSOLA for time-stretching without pitch change.
+    fn process(&mut self, _samples: &mut [f32], _sample_rate: u32) -> Result<(), FilterError> {
+        Err(FilterError::ProcessingError(
+            "Timescale changes the buffer length and must run through process_buffer".into(),
+        ))
+    }
+
+    fn process_buffer(
+        &mut self,
+        samples: &mut Vec<f32>,
+        sample_rate: u32,
+    ) -> Result<(), FilterError> {
+        if samples.len() % CHANNELS != 0 {
+            return Err(FilterError::BufferSizeMismatch);
+        }
+
+        if (self.tempo() - 1.0).abs() > f64::EPSILON {
+            self.stretcher.set_sample_rate(sample_rate);
+            let mut stretched = Vec::with_capacity((samples.len() as f64 / self.tempo()) as usize);
+            self.stretcher.process(samples, &mut stretched);
+            *samples = stretched;
+        }
+
+        if (self.resample_ratio() - 1.0).abs() > f64::EPSILON {
+            let mut resampled =
+                Vec::with_capacity((samples.len() as f64 / self.resample_ratio()) as usize + 2);
+            self.resampler.process(samples, &mut resampled);
+            *samples = resampled;
+        }
+
+        Ok(())
+    }
+
+    fn flush(&mut self, samples: &mut Vec<f32>, _sample_rate: u32) -> Result<(), FilterError> {
+        let mut tail = Vec::new();
+
+        if (self.tempo() - 1.0).abs() > f64::EPSILON {
+            self.stretcher.flush(&mut tail);
+        }
+
+        if (self.resample_ratio() - 1.0).abs() > f64::EPSILON {
+            let mut resampled = Vec::new();
+            self.resampler.process(&tail, &mut resampled);
+            self.resampler.flush(&mut resampled);
+            tail = resampled;
+        }
 
+        samples.extend_from_slice(&tail);
         Ok(())
     }
 
@@ -81,6 +384,7 @@ impl AudioFilter for TimescaleFilter {
     }
 
     fn reset(&mut self) {
-        self.accumulator = 0.0;
+        self.stretcher.reset();
+        self.resampler.reset();
     }
 }
@@ -1,14 +1,33 @@
+use super::smoothing::SmoothedValue;
 use super::{AudioFilter, FilterError};
 use std::f64::consts::PI;
 
 pub struct TremoloFilter {
     frequency: f64,
-    depth: f64,
+    depth: SmoothedValue,
     phase: f64,
 }
 
 impl TremoloFilter {
     pub fn new(frequency: f64, depth: f64) -> Result<Self, FilterError> {
+        Self::validate(frequency, depth)?;
+
+        Ok(Self {
+            frequency,
+            depth: SmoothedValue::new(depth),
+            phase: 0.0,
+        })
+    }
+
+    /// Updates the parameters without restarting the oscillator, depth glides to its new value
+    pub fn set_params(&mut self, frequency: f64, depth: f64) -> Result<(), FilterError> {
+        Self::validate(frequency, depth)?;
+        self.frequency = frequency;
+        self.depth.set(depth);
+        Ok(())
+    }
+
+    pub(super) fn validate(frequency: f64, depth: f64) -> Result<(), FilterError> {
         if frequency <= 0.0 {
             return Err(FilterError::InvalidParameter(format!(
                 "Tremolo frequency must be > 0, got {}",
@@ -21,26 +40,20 @@ impl TremoloFilter {
                 depth
             )));
         }
-
-        Ok(Self {
-            frequency,
-            depth,
-            phase: 0.0,
-        })
+        Ok(())
     }
 }
 
 impl AudioFilter for TremoloFilter {
-    fn process(&mut self, samples: &mut [i16], sample_rate: u32) -> Result<(), FilterError> {
+    fn process(&mut self, samples: &mut [f32], sample_rate: u32) -> Result<(), FilterError> {
         let phase_increment = 2.0 * PI * self.frequency / sample_rate as f64;
 
         for chunk in samples.chunks_exact_mut(2) {
-            let modulation = 1.0 - self.depth * (0.5 * (1.0 - self.phase.sin()));
+            let depth = self.depth.next(sample_rate);
+            let modulation = 1.0 - depth * (0.5 * (1.0 - self.phase.sin()));
 
-            let left =
-                (chunk[0] as f64 * modulation).clamp(i16::MIN as f64, i16::MAX as f64) as i16;
-            let right =
-                (chunk[1] as f64 * modulation).clamp(i16::MIN as f64, i16::MAX as f64) as i16;
+            let left = (chunk[0] as f64 * modulation) as f32;
+            let right = (chunk[1] as f64 * modulation) as f32;
 
             chunk[0] = left;
             chunk[1] = right;
@@ -55,7 +68,7 @@ impl AudioFilter for TremoloFilter {
     }
 
     fn is_active(&self) -> bool {
-        self.depth.abs() > f64::EPSILON
+        self.depth.is_ramping() || self.depth.current().abs() > f64::EPSILON
     }
 
     fn name(&self) -> &'static str {
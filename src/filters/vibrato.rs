@@ -1,9 +1,10 @@
+use super::smoothing::SmoothedValue;
 use super::{AudioFilter, FilterError};
 use std::f64::consts::PI;
 
 pub struct VibratoFilter {
     frequency: f64,
-    depth: f64,
+    depth: SmoothedValue,
     phase: f64,
     delay_left: Vec<f32>,
     delay_right: Vec<f32>,
@@ -14,6 +15,27 @@ impl VibratoFilter {
     const MAX_DELAY: usize = 1024;
 
     pub fn new(frequency: f64, depth: f64) -> Result<Self, FilterError> {
+        Self::validate(frequency, depth)?;
+
+        Ok(Self {
+            frequency,
+            depth: SmoothedValue::new(depth),
+            phase: 0.0,
+            delay_left: vec![0.0; Self::MAX_DELAY],
+            delay_right: vec![0.0; Self::MAX_DELAY],
+            write_pos: 0,
+        })
+    }
+
+    /// Updates the parameters while keeping the delay line, depth glides to its new value
+    pub fn set_params(&mut self, frequency: f64, depth: f64) -> Result<(), FilterError> {
+        Self::validate(frequency, depth)?;
+        self.frequency = frequency;
+        self.depth.set(depth);
+        Ok(())
+    }
+
+    pub(super) fn validate(frequency: f64, depth: f64) -> Result<(), FilterError> {
         if !(0.0..=14.0).contains(&frequency) || frequency <= 0.0 {
             return Err(FilterError::InvalidParameter(format!(
                 "Vibrato frequency must be > 0 and ≤ 14, got {}",
@@ -26,26 +48,19 @@ impl VibratoFilter {
                 depth
             )));
         }
-
-        Ok(Self {
-            frequency,
-            depth,
-            phase: 0.0,
-            delay_left: vec![0.0; Self::MAX_DELAY],
-            delay_right: vec![0.0; Self::MAX_DELAY],
-            write_pos: 0,
-        })
+        Ok(())
     }
 }
 
 impl AudioFilter for VibratoFilter {
-    fn process(&mut self, samples: &mut [i16], sample_rate: u32) -> Result<(), FilterError> {
+    fn process(&mut self, samples: &mut [f32], sample_rate: u32) -> Result<(), FilterError> {
         let phase_inc = 2.0 * PI * self.frequency / sample_rate as f64;
-        let max_delay = (Self::MAX_DELAY as f64 * self.depth * 0.5).max(1.0);
-
         for chunk in samples.chunks_exact_mut(2) {
-            self.delay_left[self.write_pos] = chunk[0] as f32;
-            self.delay_right[self.write_pos] = chunk[1] as f32;
+            let depth = self.depth.next(sample_rate);
+            let max_delay = (Self::MAX_DELAY as f64 * depth * 0.5).max(1.0);
+
+            self.delay_left[self.write_pos] = chunk[0];
+            self.delay_right[self.write_pos] = chunk[1];
 
             let delay_samples = max_delay * (0.5 + 0.5 * self.phase.sin());
 
@@ -63,8 +78,8 @@ impl AudioFilter for VibratoFilter {
             let left_out = self.delay_left[idx0] * (1.0 - frac) + self.delay_left[idx1] * frac;
             let right_out = self.delay_right[idx0] * (1.0 - frac) + self.delay_right[idx1] * frac;
 
-            chunk[0] = left_out.clamp(i16::MIN as f32, i16::MAX as f32) as i16;
-            chunk[1] = right_out.clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+            chunk[0] = left_out;
+            chunk[1] = right_out;
 
             self.write_pos = (self.write_pos + 1) % Self::MAX_DELAY;
             self.phase += phase_inc;
@@ -77,7 +92,7 @@ impl AudioFilter for VibratoFilter {
     }
 
     fn is_active(&self) -> bool {
-        self.depth.abs() > f64::EPSILON
+        self.depth.is_ramping() || self.depth.current().abs() > f64::EPSILON
     }
 
     fn name(&self) -> &'static str {
@@ -1,39 +1,54 @@
+use super::smoothing::SmoothedValue;
 use super::{AudioFilter, FilterError};
 
 pub struct VolumeFilter {
-    multiplier: f32,
+    multiplier: SmoothedValue,
 }
 
 impl VolumeFilter {
     pub fn new(volume: f64) -> Result<Self, FilterError> {
+        Self::validate(volume)?;
+
+        Ok(Self {
+            multiplier: SmoothedValue::new(volume),
+        })
+    }
+
+    pub(super) fn validate(volume: f64) -> Result<(), FilterError> {
         if !(0.0..=5.0).contains(&volume) {
             return Err(FilterError::InvalidParameter(format!(
                 "Volume must be between 0.0 and 5.0, got {}",
                 volume
             )));
         }
+        Ok(())
+    }
 
-        Ok(Self {
-            multiplier: volume as f32,
-        })
+    /// Glides to a new volume
+    pub fn set_volume(&mut self, volume: f64) -> Result<(), FilterError> {
+        Self::validate(volume)?;
+        self.multiplier.set(volume);
+        Ok(())
     }
 
     pub fn multiplier(&self) -> f32 {
-        self.multiplier
+        self.multiplier.target() as f32
     }
 }
 
 impl AudioFilter for VolumeFilter {
-    fn process(&mut self, samples: &mut [i16], _sample_rate: u32) -> Result<(), FilterError> {
-        for sample in samples.iter_mut() {
-            let adjusted = (*sample as f32) * self.multiplier;
-            *sample = adjusted.clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+    fn process(&mut self, samples: &mut [f32], sample_rate: u32) -> Result<(), FilterError> {
+        for chunk in samples.chunks_exact_mut(2) {
+            let multiplier = self.multiplier.next(sample_rate) as f32;
+            for sample in chunk.iter_mut() {
+                *sample *= multiplier;
+            }
         }
         Ok(())
     }
 
     fn is_active(&self) -> bool {
-        (self.multiplier - 1.0).abs() > f32::EPSILON
+        self.multiplier.is_ramping() || (self.multiplier.current() - 1.0).abs() > f64::EPSILON
     }
 
     fn name(&self) -> &'static str {
@@ -4,6 +4,8 @@ use std::net::{IpAddr, SocketAddr};
 use std::sync::LazyLock;
 use std::time::{Duration, Instant};
 
+use crate::middlewares::dos_protection::DOS_PROTECTION;
+use crate::middlewares::ratelimit::RATE_LIMITER;
 use crate::models::ApiNodeMessage;
 use crate::source::amazonmusic::source::AmazonMusic;
 use crate::source::applemusic::source::AppleMusic;
@@ -15,7 +17,9 @@ use crate::source::soundcloud::source::SoundCloud;
 use crate::source::spotify::source::Spotify;
 use crate::source::youtube::source::Youtube;
 use crate::util::config::Config;
+use crate::util::connection::CONNECTION_MONITOR;
 use crate::util::headers::generate_headers;
+use crate::util::logger;
 use crate::util::routeplanner::RoutePlanner;
 use crate::util::source::{FixAsyncTraitSource, Source};
 use crate::util::task::{AddTask, TasksManager};
@@ -39,8 +43,6 @@ use tokio::main;
 use tokio::net;
 use tokio::task::JoinSet;
 use tower::ServiceBuilder;
-use tracing::Level;
-use tracing_subscriber::fmt;
 
 mod constants;
 mod filters;
@@ -132,17 +134,7 @@ async fn main() {
 
     dotenv().ok();
 
-    let subscriber = fmt()
-        .pretty()
-        .with_file(true)
-        .with_line_number(true)
-        .with_thread_ids(true)
-        .with_thread_names(true)
-        .with_target(true)
-        .with_max_level(Level::DEBUG)
-        .finish();
-
-    tracing::subscriber::set_global_default(subscriber).expect("Failed to set global logger");
+    let _log_guard = logger::init(CONFIG.logging.as_ref());
 
     LazyLock::force(&CONFIG);
     LazyLock::force(&ROUTE_PLANNER);
@@ -151,6 +143,8 @@ async fn main() {
     LazyLock::force(&TASKS);
     LazyLock::force(&START);
     LazyLock::force(&REQWEST);
+    LazyLock::force(&RATE_LIMITER);
+    LazyLock::force(&DOS_PROTECTION);
 
     if CONFIG.youtube_config.is_some() {
         register_source!(Youtube, Some(REQWEST.clone()));
@@ -193,7 +187,7 @@ async fn main() {
 
     create_tasks().await;
 
-    let app = Router::new()
+    let mut app = Router::new()
         .route("/v{version}/websocket", routing::any(routes::global::ws))
         .route(
             "/v{version}/info",
@@ -223,6 +217,31 @@ async fn main() {
             "/v{version}/sessions/{session_id}/players/{guild_id}",
             routing::delete(routes::endpoints::destroy_player),
         )
+        .route(
+            "/v{version}/sessions/{session_id}/players/{guild_id}/duck",
+            routing::post(routes::endpoints::duck_player)
+                .delete(routes::endpoints::release_duck_player),
+        )
+        .route(
+            "/v{version}/sessions/{session_id}/players/{guild_id}/queue",
+            routing::get(routes::endpoints::get_queue)
+                .post(routes::endpoints::enqueue)
+                .patch(routes::endpoints::set_loop_mode)
+                .delete(routes::endpoints::clear_queue),
+        )
+        .route(
+            "/v{version}/sessions/{session_id}/players/{guild_id}/queue/move",
+            routing::post(routes::endpoints::move_queued),
+        )
+        .route(
+            "/v{version}/sessions/{session_id}/players/{guild_id}/queue/shuffle",
+            routing::post(routes::endpoints::shuffle_queue),
+        )
+        .route(
+            "/v{version}/sessions/{session_id}/players/{guild_id}/queue/{index}",
+            routing::post(routes::endpoints::insert_queued)
+                .delete(routes::endpoints::remove_queued),
+        )
         .route(
             "/v{version}/sessions/{session_id}",
             routing::patch(routes::endpoints::update_session).get(routes::endpoints::get_session),
@@ -239,10 +258,27 @@ async fn main() {
             "/v{version}/sessions",
             routing::get(routes::endpoints::get_sessions),
         )
+        .route(
+            "/v{version}/routeplanner/status",
+            routing::get(routes::routeplanner::get_status),
+        )
+        .route(
+            "/v{version}/routeplanner/free/address",
+            routing::post(routes::routeplanner::free_address),
+        )
+        .route(
+            "/v{version}/routeplanner/free/all",
+            routing::post(routes::routeplanner::free_all),
+        )
+        .route(
+            "/v{version}/dosprotection/blocklist",
+            routing::get(routes::dos_protection::get_blocklist),
+        )
         .route_layer(
             ServiceBuilder::new()
                 .layer(from_fn(middlewares::version::check))
                 .layer(from_fn(middlewares::auth::authenticate))
+                .layer(from_fn(middlewares::ratelimit::throttle))
                 .layer(from_fn(middlewares::log::request)),
         )
         .route("/version", routing::get(routes::endpoints::version))
@@ -271,6 +307,21 @@ async fn main() {
             )
         });
 
+    if CONFIG
+        .metrics
+        .as_ref()
+        .is_some_and(|metrics| metrics.enabled)
+    {
+        app = app.route(
+            "/metrics",
+            routing::get(routes::metrics::get_metrics)
+                .route_layer(from_fn(middlewares::auth::authenticate_metrics)),
+        );
+    }
+
+    // added last so it also covers the metrics route
+    app = app.layer(from_fn(middlewares::dos_protection::protect));
+
     let listener = net::TcpListener::bind(format!("{}:{}", CONFIG.address, CONFIG.port))
         .await
         .unwrap();
@@ -313,4 +364,95 @@ async fn create_tasks() {
         },
     };
     TASKS.add(task);
+
+    if let Some(config) = CONFIG
+        .connection
+        .as_ref()
+        .filter(|config| config.interval > 0)
+    {
+        let task = AddTask {
+            key: "connection_check".to_lowercase(),
+            duration: Duration::from_millis(config.interval),
+            handler: move || async move {
+                let status = CONNECTION_MONITOR.check(config).await;
+
+                let Ok(serialized) =
+                    serde_json::to_string(&ApiNodeMessage::ConnectionStatus(Box::new(status)))
+                else {
+                    return;
+                };
+
+                let set = CLIENTS
+                    .iter()
+                    .map(|client| {
+                        let message = serialized.clone();
+                        async move {
+                            let _ = client
+                                .tell(SendConnectionMessage {
+                                    message: message.into(),
+                                })
+                                .await;
+                        }
+                    })
+                    .collect::<JoinSet<()>>();
+
+                set.join_all().await;
+            },
+        };
+        TASKS.add(task);
+    }
+
+    if RATE_LIMITER.is_some() {
+        let task = AddTask {
+            key: "rate_limit_cleanup".to_lowercase(),
+            duration: Duration::from_secs(60),
+            handler: || async move {
+                if let Some(limiter) = RATE_LIMITER.as_ref() {
+                    limiter.cleanup();
+                }
+            },
+        };
+        TASKS.add(task);
+    }
+
+    if let Some(threshold) = CONFIG.zombie_threshold_ms.filter(|ms| *ms > 0) {
+        let threshold = Duration::from_millis(threshold);
+        let task = AddTask {
+            key: "zombie_player_reaper".to_lowercase(),
+            duration: threshold.min(Duration::from_secs(60)),
+            handler: move || async move {
+                voice::manager::reap_zombie_players(threshold).await;
+            },
+        };
+        TASKS.add(task);
+    }
+
+    if let Some(file) = CONFIG
+        .logging
+        .as_ref()
+        .map(|logging| &logging.file)
+        .filter(|file| file.enabled && file.ttl_days > 0)
+    {
+        let task = AddTask {
+            key: "log_file_prune".to_lowercase(),
+            duration: Duration::from_secs(60 * 60),
+            handler: move || async move {
+                logger::prune(&file.path, file.ttl_days);
+            },
+        };
+        TASKS.add(task);
+    }
+
+    if DOS_PROTECTION.is_some() {
+        let task = AddTask {
+            key: "dos_protection_cleanup".to_lowercase(),
+            duration: Duration::from_secs(60),
+            handler: || async move {
+                if let Some(protection) = DOS_PROTECTION.as_ref() {
+                    protection.cleanup();
+                }
+            },
+        };
+        TASKS.add(task);
+    }
 }
@@ -2,8 +2,29 @@ use crate::CONFIG;
 use crate::util::errors::EndpointError;
 use axum::body::Body;
 use axum::extract::Request;
-use axum::http::Response;
+use axum::http::{HeaderMap, Response};
 use axum::middleware::Next;
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+
+/// Whether the headers carry the node password, for layers that run before authenticate
+pub fn is_authorized(headers: &HeaderMap) -> bool {
+    headers
+        .get("Authorization")
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|authorization| credentials_match(authorization, &CONFIG.authorization))
+}
+
+/// Compares credentials without bailing out at the first differing byte, so timing doesn't leak how much matched
+fn credentials_match(provided: &str, expected: &str) -> bool {
+    let (provided, expected) = (provided.as_bytes(), expected.as_bytes());
+    provided.len() == expected.len()
+        && provided
+            .iter()
+            .zip(expected)
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
 
 pub async fn authenticate(request: Request, next: Next) -> Result<Response<Body>, EndpointError> {
     let authorization = request
@@ -12,7 +33,37 @@ pub async fn authenticate(request: Request, next: Next) -> Result<Response<Body>
         .ok_or(EndpointError::MissingOption("Authorization"))?
         .to_str()?;
 
-    if authorization != CONFIG.authorization {
+    if !credentials_match(authorization, &CONFIG.authorization) {
+        return Err(EndpointError::Unauthorized);
+    }
+
+    Ok(next.run(request).await)
+}
+
+pub async fn authenticate_metrics(
+    request: Request,
+    next: Next,
+) -> Result<Response<Body>, EndpointError> {
+    let Some(config) = CONFIG.metrics.as_ref() else {
+        return Err(EndpointError::Unauthorized);
+    };
+
+    let credentials = format!(
+        "{}:{}",
+        config.authorization.username, config.authorization.password
+    );
+    let expected = format!("Basic {}", BASE64_STANDARD.encode(credentials));
+
+    // a 401 with a challenge makes browsers and scrapers send the credentials
+    let authorization = request
+        .headers()
+        .get("Authorization")
+        .ok_or(EndpointError::AuthenticationRequired(
+            "Basic realm=\"metrics\"",
+        ))?
+        .to_str()?;
+
+    if !credentials_match(authorization, &expected) {
         return Err(EndpointError::Unauthorized);
     }
 
@@ -0,0 +1,198 @@
+use crate::CONFIG;
+use crate::middlewares::auth::is_authorized;
+use crate::util::errors::EndpointError;
+use crate::util::sliding_window::SlidingWindow;
+use axum::body::Body;
+use axum::extract::{ConnectInfo, Request};
+use axum::http::Response;
+use axum::middleware::Next;
+use dashmap::DashMap;
+use serde::Serialize;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::LazyLock;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+struct Offender {
+    delayed: u32,
+    last_seen: Instant,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockedAddress {
+    pub address: String,
+    pub blocked_until: u64,
+    pub remaining_ms: u64,
+}
+
+/// Per ip burst detector, offenders are delayed first then blocked if they keep bursting
+pub struct DosProtection {
+    burst_requests: u32,
+    window: SlidingWindow,
+    time_window: Duration,
+    delay: Duration,
+    block_duration: Duration,
+    offenders: DashMap<IpAddr, Offender>,
+    blocked: DashMap<IpAddr, Instant>,
+}
+
+enum Verdict {
+    Allow,
+    Delay(Duration),
+    Block(Duration),
+}
+
+impl DosProtection {
+    fn inspect(&self, ip: IpAddr) -> Verdict {
+        let now = Instant::now();
+
+        if let Some(until) = self.blocked.get(&ip).map(|until| *until) {
+            if until > now {
+                return Verdict::Block(until - now);
+            }
+            self.blocked.remove(&ip);
+        }
+
+        let key = ip.to_string();
+        let bursting = self.window.retry_after(&key).is_some();
+
+        self.window.hit(&key);
+
+        if !bursting {
+            self.offenders.remove_if(&ip, |_, offender| {
+                now.duration_since(offender.last_seen) >= self.time_window
+            });
+            return Verdict::Allow;
+        }
+
+        let mut offender = self.offenders.entry(ip).or_insert(Offender {
+            delayed: 0,
+            last_seen: now,
+        });
+
+        offender.delayed += 1;
+        offender.last_seen = now;
+
+        if offender.delayed <= self.burst_requests {
+            return Verdict::Delay(self.delay);
+        }
+
+        drop(offender);
+
+        self.offenders.remove(&ip);
+        self.blocked.insert(ip, now + self.block_duration);
+
+        tracing::warn!(
+            "DosProtection: Blocked {} for {:?} after repeated bursts",
+            ip,
+            self.block_duration
+        );
+
+        Verdict::Block(self.block_duration)
+    }
+
+    pub fn blocklist(&self) -> Vec<BlockedAddress> {
+        let now = Instant::now();
+        let epoch_now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        self.blocked
+            .iter()
+            .filter(|entry| *entry.value() > now)
+            .map(|entry| {
+                let remaining = *entry.value() - now;
+                BlockedAddress {
+                    address: entry.key().to_string(),
+                    blocked_until: (epoch_now + remaining).as_millis() as u64,
+                    remaining_ms: remaining.as_millis() as u64,
+                }
+            })
+            .collect()
+    }
+
+    pub fn cleanup(&self) {
+        let now = Instant::now();
+        self.window.cleanup();
+        self.blocked.retain(|_, until| *until > now);
+        self.offenders
+            .retain(|_, offender| now.duration_since(offender.last_seen) < self.time_window);
+    }
+}
+
+pub static DOS_PROTECTION: LazyLock<Option<DosProtection>> = LazyLock::new(|| {
+    let config = CONFIG
+        .dos_protection
+        .as_ref()
+        .filter(|config| config.enabled)?;
+
+    let time_window = Duration::from_millis(config.thresholds.time_window_ms);
+
+    Some(DosProtection {
+        burst_requests: config.thresholds.burst_requests,
+        window: SlidingWindow::new(config.thresholds.burst_requests, time_window),
+        time_window,
+        delay: Duration::from_millis(config.mitigation.delay_ms),
+        block_duration: Duration::from_millis(config.mitigation.block_duration_ms),
+        offenders: DashMap::new(),
+        blocked: DashMap::new(),
+    })
+});
+
+fn guild_id_from_path(path: &str) -> Option<&str> {
+    let mut segments = path.split('/');
+    segments.find(|segment| *segment == "players")?;
+    segments.next().filter(|segment| !segment.is_empty())
+}
+
+pub async fn protect(
+    ConnectInfo(address): ConnectInfo<SocketAddr>,
+    request: Request,
+    next: Next,
+) -> Result<Response<Body>, EndpointError> {
+    let (Some(protection), Some(config)) =
+        (DOS_PROTECTION.as_ref(), CONFIG.dos_protection.as_ref())
+    else {
+        return Ok(next.run(request).await);
+    };
+
+    let ip = address.ip();
+
+    // this layer runs before authenticate, so user and guild exemptions need the node password
+    // or anyone could claim a known bot's User-Id
+    let exempt_client = is_authorized(request.headers())
+        && (request
+            .headers()
+            .get("User-Id")
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|id| config.ignore.user_ids.iter().any(|ignored| ignored == id))
+            || guild_id_from_path(request.uri().path())
+                .is_some_and(|id| config.ignore.guild_ids.iter().any(|ignored| ignored == id)));
+
+    let ignored = config.ignore.ips.contains(&ip.to_string()) || exempt_client;
+
+    if ignored {
+        return Ok(next.run(request).await);
+    }
+
+    match protection.inspect(ip) {
+        Verdict::Allow => {}
+        Verdict::Delay(delay) => {
+            tracing::debug!(
+                "DosProtection: Delaying a request from {} by {:?}: [Method: {}] [Endpoint: {}]",
+                ip,
+                delay,
+                request.method(),
+                request.uri()
+            );
+            tokio::time::sleep(delay).await;
+        }
+        Verdict::Block(remaining) => {
+            return Err(EndpointError::TemporarilyBlocked(
+                (remaining.as_secs_f64().ceil() as u64).max(1),
+            ));
+        }
+    }
+
+    Ok(next.run(request).await)
+}
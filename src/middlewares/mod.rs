@@ -1,3 +1,5 @@
 pub mod auth;
+pub mod dos_protection;
 pub mod log;
+pub mod ratelimit;
 pub mod version;
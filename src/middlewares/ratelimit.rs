@@ -0,0 +1,130 @@
+use crate::CONFIG;
+use crate::util::errors::EndpointError;
+use crate::util::sliding_window::SlidingWindow;
+use axum::body::Body;
+use axum::extract::{ConnectInfo, Path, Request};
+use axum::http::Response;
+use axum::middleware::Next;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+pub struct RateLimiter {
+    global: SlidingWindow,
+    per_ip: SlidingWindow,
+    per_user_id: SlidingWindow,
+    per_guild_id: SlidingWindow,
+}
+
+impl RateLimiter {
+    pub fn cleanup(&self) {
+        self.global.cleanup();
+        self.per_ip.cleanup();
+        self.per_user_id.cleanup();
+        self.per_guild_id.cleanup();
+    }
+}
+
+pub static RATE_LIMITER: LazyLock<Option<RateLimiter>> = LazyLock::new(|| {
+    let config = CONFIG.rate_limit.as_ref().filter(|config| config.enabled)?;
+
+    Some(RateLimiter {
+        global: SlidingWindow::new(
+            config.global.max_requests,
+            Duration::from_millis(config.global.time_window_ms),
+        ),
+        per_ip: SlidingWindow::new(
+            config.per_ip.max_requests,
+            Duration::from_millis(config.per_ip.time_window_ms),
+        ),
+        per_user_id: SlidingWindow::new(
+            config.per_user_id.max_requests,
+            Duration::from_millis(config.per_user_id.time_window_ms),
+        ),
+        per_guild_id: SlidingWindow::new(
+            config.per_guild_id.max_requests,
+            Duration::from_millis(config.per_guild_id.time_window_ms),
+        ),
+    })
+});
+
+pub async fn throttle(
+    Path(params): Path<HashMap<String, String>>,
+    ConnectInfo(address): ConnectInfo<SocketAddr>,
+    request: Request,
+    next: Next,
+) -> Result<Response<Body>, EndpointError> {
+    let (Some(limiter), Some(config)) = (RATE_LIMITER.as_ref(), CONFIG.rate_limit.as_ref()) else {
+        return Ok(next.run(request).await);
+    };
+
+    let path = request.uri().path();
+
+    if config
+        .ignore_paths
+        .iter()
+        .any(|ignored| path.starts_with(ignored.as_str()))
+    {
+        return Ok(next.run(request).await);
+    }
+
+    let ip = address.ip().to_string();
+
+    let user_id = request
+        .headers()
+        .get("User-Id")
+        .and_then(|value| value.to_str().ok())
+        .map(String::from);
+
+    let guild_id = params.get("guild_id").cloned();
+
+    let ignored = config.ignore.ips.contains(&ip)
+        || user_id
+            .as_ref()
+            .is_some_and(|id| config.ignore.user_ids.contains(id))
+        || guild_id
+            .as_ref()
+            .is_some_and(|id| config.ignore.guild_ids.contains(id));
+
+    if ignored {
+        return Ok(next.run(request).await);
+    }
+
+    let mut buckets = vec![
+        (&limiter.global, "global".to_string()),
+        (&limiter.per_ip, ip),
+    ];
+
+    if let Some(user_id) = user_id {
+        buckets.push((&limiter.per_user_id, user_id));
+    }
+
+    if let Some(guild_id) = guild_id {
+        buckets.push((&limiter.per_guild_id, guild_id));
+    }
+
+    let retry_after = buckets
+        .iter()
+        .filter_map(|(window, key)| window.retry_after(key))
+        .max();
+
+    if let Some(retry_after) = retry_after {
+        tracing::warn!(
+            "Rate limited a request from {}: [Method: {}] [Endpoint: {}] [RetryAfter: {:?}]",
+            address.ip(),
+            request.method(),
+            request.uri(),
+            retry_after
+        );
+        return Err(EndpointError::TooManyRequests(
+            (retry_after.as_secs_f64().ceil() as u64).max(1),
+        ));
+    }
+
+    for (window, key) in &buckets {
+        window.hit(key);
+    }
+
+    Ok(next.run(request).await)
+}
@@ -22,6 +22,14 @@ where
     se.serialize_str(num.to_string().as_str())
 }
 
+fn null_to_default<'de, T, D>(de: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(de)?.unwrap_or_default())
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Severity {
@@ -133,11 +141,20 @@ pub struct ApiTrackInfo {
 pub struct ApiTrack {
     pub encoded: String,
     pub info: ApiTrackInfo,
-    pub plugin_info: Empty,
+    #[serde(default, deserialize_with = "null_to_default")]
+    pub plugin_info: ApiTrackPluginInfo,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user_data: Option<Value>,
 }
 
+/// Extra track data outside of the Lavalink track info, serialized as an empty object when unset
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ApiTrackPluginInfo {
+    /// Set on tracks autoplay picked rather than the client
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub autoplay: Option<bool>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ApiException {
     #[serde(deserialize_with = "str_to_u64", serialize_with = "u64_to_str")]
@@ -155,13 +172,23 @@ pub struct ApiTrackStart {
     pub track: ApiTrack,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Reply, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ApiTrackEndReason {
+    Finished,
+    LoadFailed,
+    Stopped,
+    Replaced,
+    Cleanup,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiTrackEnd {
     #[serde(deserialize_with = "str_to_u64", serialize_with = "u64_to_str")]
     pub guild_id: u64,
     pub track: ApiTrack,
-    pub reason: String,
+    pub reason: ApiTrackEndReason,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -192,6 +219,16 @@ pub struct ApiWebSocketClosed {
     pub by_remote: bool,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiQueueUpdate {
+    #[serde(deserialize_with = "str_to_u64", serialize_with = "u64_to_str")]
+    pub guild_id: u64,
+    pub track: Option<ApiTrack>,
+    pub tracks: Vec<ApiTrack>,
+    pub loop_mode: ApiLoopMode,
+}
+
 #[allow(clippy::enum_variant_names)]
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -201,6 +238,7 @@ pub enum ApiPlayerEvents {
     TrackExceptionEvent(ApiTrackException),
     TrackStuckEvent(ApiTrackStuck),
     WebSocketClosedEvent(ApiWebSocketClosed),
+    QueueUpdateEvent(ApiQueueUpdate),
 }
 
 #[derive(Default, Clone, Debug, Serialize, Deserialize)]
@@ -213,6 +251,35 @@ pub struct UpdateApiPlayerTrack {
     pub user_data: Option<Value>,
 }
 
+/// What the player does when a track finishes on its own
+#[derive(Default, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ApiLoopMode {
+    #[default]
+    Off,
+    Track,
+    Queue,
+}
+
+#[derive(Clone, Debug, Reply, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiQueue {
+    pub tracks: Vec<ApiTrack>,
+    pub loop_mode: ApiLoopMode,
+}
+
+/// A track to queue, either already encoded or an identifier to resolve
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiQueueTrack {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoded: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_data: Option<Value>,
+}
+
 #[derive(Default, Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiPlayerOptions {
@@ -232,6 +299,12 @@ pub struct ApiPlayerOptions {
     pub voice: Option<ApiVoiceData>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub filters: Option<LavalinkFilters>,
+    /// Whether the player picks related tracks by itself once playback runs dry
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub autoplay: Option<bool>,
+    /// Track the client expects to play next, preloaded so its play starts without delay
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_track: Option<UpdateApiPlayerTrack>,
 }
 
 #[derive(Default, Clone, Debug, Serialize, Deserialize)]
@@ -281,6 +354,25 @@ pub struct ApiStats {
     pub memory: ApiMemory,
     pub cpu: ApiCpu,
     pub frame_stats: Option<ApiFrameStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connection: Option<ApiConnectionStatus>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ApiConnectionQuality {
+    Good,
+    Average,
+    Bad,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiConnectionStatus {
+    pub status: ApiConnectionQuality,
+    pub latency: Option<f64>,
+    pub target: String,
+    pub checked_at: u64,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -291,6 +383,7 @@ pub enum ApiNodeMessage {
     PlayerUpdate(Box<ApiPlayerUpdate>),
     Stats(Box<ApiStats>),
     Event(Box<ApiPlayerEvents>),
+    ConnectionStatus(Box<ApiConnectionStatus>),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -365,21 +458,21 @@ impl LavalinkFilters {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Tremolo {
     pub frequency: Option<f64>,
     pub depth: Option<f64>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Vibrato {
     pub frequency: Option<f64>,
     pub depth: Option<f64>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Timescale {
     pub speed: Option<f64>,
@@ -387,19 +480,19 @@ pub struct Timescale {
     pub rate: Option<f64>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Rotation {
     pub rotation_hz: Option<f64>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LowPass {
     pub smoothing: Option<f64>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Karaoke {
     pub level: Option<f64>,
@@ -408,14 +501,14 @@ pub struct Karaoke {
     pub filter_width: Option<f64>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Equalizer {
     pub band: u16,
     pub gain: f64,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Distortion {
     pub sin_offset: Option<f64>,
@@ -428,7 +521,7 @@ pub struct Distortion {
     pub scale: Option<f64>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ChannelMix {
     pub left_to_left: Option<f64>,
@@ -436,3 +529,86 @@ pub struct ChannelMix {
     pub right_to_left: Option<f64>,
     pub right_to_right: Option<f64>,
 }
+
+/// Filters carried in `pluginFilters`, accepting both the NodeLink and LavaDSPX shapes
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginFilters {
+    #[serde(alias = "highpass", alias = "high-pass")]
+    pub high_pass: Option<HighPass>,
+    pub chorus: Option<Chorus>,
+    pub compressor: Option<Compressor>,
+    pub echo: Option<Echo>,
+    pub phaser: Option<Phaser>,
+    #[serde(alias = "loudness")]
+    pub normalization: Option<Normalization>,
+    pub skip_silence: Option<SkipSilence>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HighPass {
+    pub cutoff_frequency: Option<f64>,
+    pub boost_factor: Option<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Chorus {
+    pub rate: Option<f64>,
+    pub depth: Option<f64>,
+    pub delay: Option<f64>,
+    pub mix: Option<f64>,
+    pub feedback: Option<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Compressor {
+    pub threshold: Option<f64>,
+    pub ratio: Option<f64>,
+    pub attack: Option<f64>,
+    pub release: Option<f64>,
+    #[serde(alias = "makeupGain")]
+    pub gain: Option<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Echo {
+    /// LavaDSPX delay in seconds
+    pub echo_length: Option<f64>,
+    /// NodeLink delay in milliseconds
+    pub delay: Option<f64>,
+    #[serde(alias = "feedback")]
+    pub decay: Option<f64>,
+    pub mix: Option<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Phaser {
+    pub stages: Option<u32>,
+    pub rate: Option<f64>,
+    pub depth: Option<f64>,
+    pub feedback: Option<f64>,
+    pub mix: Option<f64>,
+    pub min_frequency: Option<f64>,
+    pub max_frequency: Option<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Normalization {
+    pub enabled: Option<bool>,
+    /// Integrated loudness to normalize to, in LUFS
+    pub target: Option<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkipSilence {
+    pub enabled: Option<bool>,
+    /// Level in dBFS below which leading and trailing audio counts as silence
+    pub threshold: Option<f64>,
+}
@@ -0,0 +1,21 @@
+use crate::middlewares::dos_protection::DOS_PROTECTION;
+use crate::util::errors::EndpointError;
+use axum::body::Body;
+use axum::http::StatusCode;
+use axum::response::Response;
+
+pub async fn get_blocklist() -> Result<Response<Body>, EndpointError> {
+    let Some(protection) = DOS_PROTECTION.as_ref() else {
+        return Response::builder()
+            .status(StatusCode::NO_CONTENT)
+            .body(Body::empty())
+            .map_err(|e| EndpointError::FailedMessage(e.to_string()));
+    };
+
+    let string = serde_json::to_string_pretty(&protection.blocklist())?;
+
+    Response::builder()
+        .header("Content-Type", "application/json")
+        .body(Body::from(string))
+        .map_err(|e| EndpointError::FailedMessage(e.to_string()))
+}
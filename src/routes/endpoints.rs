@@ -1,21 +1,28 @@
 use super::DecodeQueryString;
+use super::DuckBody;
 use super::EncodeQueryString;
 use super::PlayerMethodsPath;
 use super::PlayerUpdateQuery;
 use super::SessionMethodsPath;
+use super::{QueueIndexPath, QueueLoopBody, QueueMoveBody, QueueTracksBody};
 use crate::CLIENTS;
+use crate::CONFIG;
 use crate::SOURCES;
+use crate::filters::processor::FilterChain;
 use crate::models::{
-    ApiPlayerOptions, ApiSessionBody, ApiSessionInfo, ApiTrack, ApiTrackResult, Empty,
+    ApiPlayerOptions, ApiQueue, ApiQueueTrack, ApiSessionBody, ApiSessionInfo, ApiTrack,
+    ApiTrackResult,
 };
 use crate::util::api_stats;
 use crate::util::converter::numbers::FromU64;
 use crate::util::decoder::{decode_base64, decode_track};
 use crate::util::errors::EndpointError;
+use crate::util::source::resolve_identifier;
 use crate::voice::manager::CreatePlayerOptions;
 use crate::voice::player::{
-    GetApiPlayerInfo, GetTrackHandle, IsActive, Pause, Play, Seek, SetEndTimeTask, SetFilters,
-    SetVolume, Stop,
+    ClearQueue, Duck, Enqueue, GetApiPlayerInfo, GetQueue, GetTrackHandle, IsActive, MoveQueued,
+    Pause, Play, PlaybackRate, Player, Preload, PreloadIdentifier, ReleaseDuck, RemoveQueued, Seek,
+    SetAutoplay, SetEndTimeTask, SetFilters, SetLoopMode, SetVolume, ShuffleQueue, Stop,
 };
 use crate::ws::client::{
     CreatePlayer, DestroyPlayer, GetPlayer, GetWebsocketInfo, UpdateWebsocket, WebSocketClient,
@@ -24,6 +31,7 @@ use axum::Json;
 use axum::body::Body;
 use axum::extract::Path;
 use axum::extract::Query;
+use axum::http::StatusCode;
 use axum::response::Response;
 use dashmap::mapref::multiple::RefMulti;
 use kameo::actor::ActorRef;
@@ -47,22 +55,29 @@ async fn get_client(
     None
 }
 
-pub async fn get_player(
-    Path(PlayerMethodsPath {
-        session_id,
-        guild_id,
-    }): Path<PlayerMethodsPath>,
-) -> Result<Response<Body>, EndpointError> {
+async fn get_player_ref(
+    session_id: String,
+    guild_id: u64,
+) -> Result<ActorRef<Player>, EndpointError> {
     let client = get_client(session_id)
         .await
         .ok_or(EndpointError::NoWebsocketClientFound)?;
 
-    let player = client
+    client
         .ask(GetPlayer {
             guild_id: GuildId::from_u64(guild_id),
         })
         .await?
-        .ok_or(EndpointError::NoPlayerFound)?;
+        .ok_or(EndpointError::NoPlayerFound)
+}
+
+pub async fn get_player(
+    Path(PlayerMethodsPath {
+        session_id,
+        guild_id,
+    }): Path<PlayerMethodsPath>,
+) -> Result<Response<Body>, EndpointError> {
+    let player = get_player_ref(session_id, guild_id).await?;
 
     let data = player.ask(GetApiPlayerInfo).await?;
 
@@ -79,6 +94,14 @@ pub async fn update_player(
     }): Path<PlayerMethodsPath>,
     Json(update_player): Json<ApiPlayerOptions>,
 ) -> Result<Response<Body>, EndpointError> {
+    if let Some(filters) = update_player.filters.as_ref() {
+        let disabled = FilterChain::disabled_filters(filters);
+
+        if !disabled.is_empty() {
+            return Err(EndpointError::DisabledFilters(disabled.join(", ")));
+        }
+    }
+
     let client = get_client(session_id)
         .await
         .ok_or(EndpointError::NoWebsocketClientFound)?;
@@ -136,25 +159,9 @@ pub async fn update_player(
         .and_then(|t| t.identifier.clone())
         .or(update_player.identifier.clone())
     {
-        let mut resolved: Option<String> = None;
-        for source in SOURCES.iter() {
-            let Some(data) = source.to_inner_ref().parse_query(&identifier) else {
-                continue;
-            };
-            let result = source
-                .to_inner_ref()
-                .resolve(data)
-                .await
-                .ok()
-                .flatten()
-                .unwrap_or(ApiTrackResult::Empty(None));
-
-            if let ApiTrackResult::Track(api_track) = result {
-                resolved = Some(api_track.encoded);
-                break;
-            }
-        }
-        resolved
+        resolve_identifier(&identifier)
+            .await
+            .map(|api_track| api_track.encoded)
     } else if let Some(track) = update_player.track.as_ref() {
         if let Value::Null = track.encoded {
             player.ask(Stop).await?;
@@ -201,6 +208,25 @@ pub async fn update_player(
         }
     }
 
+    if let Some(autoplay) = update_player.autoplay {
+        player.ask(SetAutoplay { autoplay }).await?;
+    }
+
+    if let Some(next_track) = update_player.next_track {
+        // a null encoded track without an identifier clears the hint
+        match (next_track.encoded, next_track.identifier) {
+            (Value::String(encoded), _) => {
+                player
+                    .ask(Preload {
+                        encoded: Some(encoded),
+                    })
+                    .await?
+            }
+            (_, Some(identifier)) => player.ask(PreloadIdentifier { identifier }).await?,
+            _ => player.ask(Preload { encoded: None }).await?,
+        }
+    }
+
     let track_uuid = player
         .ask(GetTrackHandle)
         .await
@@ -222,7 +248,18 @@ pub async fn update_player(
             } else {
                 let player_ref = player.clone();
                 let task = spawn(async move {
-                    sleep(Duration::from_millis(remaining_ms)).await;
+                    let mut remaining_ms = remaining_ms;
+                    // re-checks the source position since timescale and pauses change how fast it moves
+                    while remaining_ms > 0 {
+                        let rate = player_ref.ask(PlaybackRate).await.unwrap_or(1.0).max(0.01);
+                        let wait = (remaining_ms as f64 / rate).clamp(1.0, 1000.0);
+                        sleep(Duration::from_millis(wait as u64)).await;
+
+                        let Ok(info) = player_ref.ask(GetApiPlayerInfo).await else {
+                            return;
+                        };
+                        remaining_ms = (end_ms as u64).saturating_sub(info.state.position as u64);
+                    }
                     if let Ok(Some(current_handle)) = player_ref.ask(GetTrackHandle).await {
                         if current_handle.uuid() == uuid {
                             player_ref.ask(Stop).await.ok();
@@ -263,6 +300,211 @@ pub async fn destroy_player(
     Ok(Response::new(Body::from(())))
 }
 
+pub async fn duck_player(
+    Path(PlayerMethodsPath {
+        session_id,
+        guild_id,
+    }): Path<PlayerMethodsPath>,
+    body: Option<Json<DuckBody>>,
+) -> Result<Response<Body>, EndpointError> {
+    if !CONFIG
+        .audio
+        .as_ref()
+        .is_some_and(|audio| audio.fading.ducking.enabled)
+    {
+        return Err(EndpointError::DuckingDisabled);
+    }
+
+    let player = get_player_ref(session_id, guild_id).await?;
+
+    player
+        .ask(Duck {
+            duration: body.and_then(|Json(body)| body.duration),
+        })
+        .await?;
+
+    Response::builder()
+        .status(StatusCode::NO_CONTENT)
+        .body(Body::empty())
+        .map_err(|e| EndpointError::FailedMessage(e.to_string()))
+}
+
+pub async fn release_duck_player(
+    Path(PlayerMethodsPath {
+        session_id,
+        guild_id,
+    }): Path<PlayerMethodsPath>,
+) -> Result<Response<Body>, EndpointError> {
+    if !CONFIG
+        .audio
+        .as_ref()
+        .is_some_and(|audio| audio.fading.ducking.enabled)
+    {
+        return Err(EndpointError::DuckingDisabled);
+    }
+
+    let player = get_player_ref(session_id, guild_id).await?;
+
+    player.ask(ReleaseDuck).await?;
+
+    Response::builder()
+        .status(StatusCode::NO_CONTENT)
+        .body(Body::empty())
+        .map_err(|e| EndpointError::FailedMessage(e.to_string()))
+}
+
+fn queue_response(queue: Option<ApiQueue>) -> Result<Response<Body>, EndpointError> {
+    let queue = queue.ok_or(EndpointError::UnprocessableEntity(
+        "Queue index out of range",
+    ))?;
+
+    let string = serde_json::to_string_pretty(&queue)?;
+
+    Ok(Response::new(Body::from(string)))
+}
+
+/// Turns queue entries into tracks, decoding encoded ones and resolving identifiers
+async fn to_queue_tracks(tracks: Vec<ApiQueueTrack>) -> Result<Vec<ApiTrack>, EndpointError> {
+    let mut resolved = Vec::with_capacity(tracks.len());
+
+    for track in tracks {
+        let mut api_track = if let Some(encoded) = track.encoded {
+            let info = decode_track(&encoded).or_else(|_| decode_base64(&encoded))?;
+            ApiTrack {
+                encoded,
+                info,
+                plugin_info: Default::default(),
+                user_data: None,
+            }
+        } else if let Some(identifier) = track.identifier {
+            resolve_identifier(&identifier)
+                .await
+                .ok_or(EndpointError::UnprocessableEntity(
+                    "Identifier did not resolve to a track",
+                ))?
+        } else {
+            return Err(EndpointError::UnprocessableEntity(
+                "Queued tracks need either encoded or identifier",
+            ));
+        };
+
+        api_track.user_data = track.user_data;
+        resolved.push(api_track);
+    }
+
+    Ok(resolved)
+}
+
+pub async fn get_queue(
+    Path(PlayerMethodsPath {
+        session_id,
+        guild_id,
+    }): Path<PlayerMethodsPath>,
+) -> Result<Response<Body>, EndpointError> {
+    let player = get_player_ref(session_id, guild_id).await?;
+
+    queue_response(Some(player.ask(GetQueue).await?))
+}
+
+pub async fn enqueue(
+    Path(PlayerMethodsPath {
+        session_id,
+        guild_id,
+    }): Path<PlayerMethodsPath>,
+    Json(body): Json<QueueTracksBody>,
+) -> Result<Response<Body>, EndpointError> {
+    let player = get_player_ref(session_id, guild_id).await?;
+    let tracks = to_queue_tracks(body.tracks).await?;
+
+    queue_response(
+        player
+            .ask(Enqueue {
+                tracks,
+                index: None,
+            })
+            .await?,
+    )
+}
+
+pub async fn insert_queued(
+    Path(QueueIndexPath {
+        session_id,
+        guild_id,
+        index,
+    }): Path<QueueIndexPath>,
+    Json(body): Json<QueueTracksBody>,
+) -> Result<Response<Body>, EndpointError> {
+    let player = get_player_ref(session_id, guild_id).await?;
+    let tracks = to_queue_tracks(body.tracks).await?;
+
+    queue_response(
+        player
+            .ask(Enqueue {
+                tracks,
+                index: Some(index),
+            })
+            .await?,
+    )
+}
+
+pub async fn remove_queued(
+    Path(QueueIndexPath {
+        session_id,
+        guild_id,
+        index,
+    }): Path<QueueIndexPath>,
+) -> Result<Response<Body>, EndpointError> {
+    let player = get_player_ref(session_id, guild_id).await?;
+
+    queue_response(player.ask(RemoveQueued { index }).await?)
+}
+
+pub async fn move_queued(
+    Path(PlayerMethodsPath {
+        session_id,
+        guild_id,
+    }): Path<PlayerMethodsPath>,
+    Json(QueueMoveBody { from, to }): Json<QueueMoveBody>,
+) -> Result<Response<Body>, EndpointError> {
+    let player = get_player_ref(session_id, guild_id).await?;
+
+    queue_response(player.ask(MoveQueued { from, to }).await?)
+}
+
+pub async fn shuffle_queue(
+    Path(PlayerMethodsPath {
+        session_id,
+        guild_id,
+    }): Path<PlayerMethodsPath>,
+) -> Result<Response<Body>, EndpointError> {
+    let player = get_player_ref(session_id, guild_id).await?;
+
+    queue_response(Some(player.ask(ShuffleQueue).await?))
+}
+
+pub async fn clear_queue(
+    Path(PlayerMethodsPath {
+        session_id,
+        guild_id,
+    }): Path<PlayerMethodsPath>,
+) -> Result<Response<Body>, EndpointError> {
+    let player = get_player_ref(session_id, guild_id).await?;
+
+    queue_response(Some(player.ask(ClearQueue).await?))
+}
+
+pub async fn set_loop_mode(
+    Path(PlayerMethodsPath {
+        session_id,
+        guild_id,
+    }): Path<PlayerMethodsPath>,
+    Json(QueueLoopBody { loop_mode }): Json<QueueLoopBody>,
+) -> Result<Response<Body>, EndpointError> {
+    let player = get_player_ref(session_id, guild_id).await?;
+
+    queue_response(Some(player.ask(SetLoopMode { loop_mode }).await?))
+}
+
 #[tracing::instrument]
 pub async fn update_session(
     Path(SessionMethodsPath { session_id }): Path<SessionMethodsPath>,
@@ -315,7 +557,7 @@ pub async fn decode(query: Query<DecodeQueryString>) -> Result<Response<Body>, E
     let track = ApiTrack {
         encoded: query.track.clone(),
         info: track,
-        plugin_info: Empty,
+        plugin_info: Default::default(),
         user_data: None,
     };
 
@@ -353,7 +595,7 @@ pub async fn decode_tracks(
             Ok(ApiTrack {
                 encoded,
                 info,
-                plugin_info: Empty,
+                plugin_info: Default::default(),
                 user_data: None,
             })
         })
@@ -378,7 +620,6 @@ pub async fn encode(query: Query<EncodeQueryString>) -> Result<Response<Body>, E
         tracing::info!("Trying source: {}", source.to_inner_ref().get_name());
 
         track = source
-            .to_inner_ref()
             .resolve(data)
             .await?
             .unwrap_or(ApiTrackResult::Empty(None));
@@ -400,6 +641,12 @@ pub async fn encode(query: Query<EncodeQueryString>) -> Result<Response<Body>, E
 pub async fn node_info() -> Result<Response<Body>, EndpointError> {
     let sources: Vec<String> = SOURCES.iter().map(|entry| entry.key().clone()).collect();
 
+    let filters: Vec<&str> = FilterChain::availability()
+        .into_iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(name, _)| name)
+        .collect();
+
     let info = serde_json::json!({
         "version": {
             "semver": "4.0.0",
@@ -416,18 +663,7 @@ pub async fn node_info() -> Result<Response<Body>, EndpointError> {
             "commitTime": 0
         },
         "sourceManagers": sources,
-        "filters": [
-            "volume",
-            "equalizer",
-            "timescale",
-            "tremolo",
-            "vibrato",
-            "rotation",
-            "distortion",
-            "channelMix",
-            "lowPass",
-            "karaoke"
-        ],
+        "filters": filters,
     });
 
     let string = serde_json::to_string_pretty(&info)?;
@@ -0,0 +1,43 @@
+use crate::util::api_stats;
+use crate::util::errors::EndpointError;
+use crate::util::metrics::{self, RoutePlannerMetrics, WebsocketMetrics};
+use crate::ws::client::GetWebsocketInfo;
+use crate::{CLIENTS, ROUTE_PLANNER};
+use axum::body::Body;
+use axum::response::Response;
+
+pub async fn get_metrics() -> Result<Response<Body>, EndpointError> {
+    let stats = api_stats::get_stats().await;
+
+    let mut websocket = WebsocketMetrics {
+        connected: 0,
+        disconnected: 0,
+        queued_messages: 0,
+    };
+
+    for client_ref in CLIENTS.iter() {
+        let Ok(data) = client_ref.ask(GetWebsocketInfo).await else {
+            continue;
+        };
+
+        if data.connected {
+            websocket.connected += 1;
+        } else {
+            websocket.disconnected += 1;
+        }
+
+        websocket.queued_messages += data.queued_messages as u64;
+    }
+
+    let route_planner = ROUTE_PLANNER.as_ref().map(|planner| RoutePlannerMetrics {
+        banned_ips: planner.banned_count() as u64,
+        available_ips: planner.available_ips(),
+    });
+
+    let body = metrics::render(&stats, &websocket, route_planner.as_ref());
+
+    Response::builder()
+        .header("Content-Type", "text/plain; version=0.0.4; charset=utf-8")
+        .body(Body::from(body))
+        .map_err(|e| EndpointError::FailedMessage(e.to_string()))
+}
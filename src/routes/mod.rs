@@ -1,7 +1,11 @@
+use crate::models::{ApiLoopMode, ApiQueueTrack};
 use serde::Deserialize;
 
+pub mod dos_protection;
 pub mod endpoints;
 pub mod global;
+pub mod metrics;
+pub mod routeplanner;
 pub mod youtube;
 
 #[derive(Deserialize, Debug)]
@@ -21,6 +25,35 @@ pub struct PlayerUpdateQuery {
     pub no_replace: Option<bool>,
 }
 
+#[derive(Deserialize, Debug)]
+pub struct DuckBody {
+    pub duration: Option<u64>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct QueueIndexPath {
+    pub session_id: String,
+    pub guild_id: u64,
+    pub index: usize,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct QueueTracksBody {
+    pub tracks: Vec<ApiQueueTrack>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct QueueMoveBody {
+    pub from: usize,
+    pub to: usize,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueLoopBody {
+    pub loop_mode: ApiLoopMode,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct DecodeQueryString {
     pub track: String,
@@ -0,0 +1,63 @@
+use crate::ROUTE_PLANNER;
+use crate::util::errors::EndpointError;
+use crate::util::routeplanner::RoutePlannerStatus;
+use axum::Json;
+use axum::body::Body;
+use axum::http::StatusCode;
+use axum::response::Response;
+use serde::Deserialize;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+#[derive(Deserialize, Debug)]
+pub struct FreeAddressBody {
+    pub address: String,
+}
+
+pub async fn get_status() -> Result<Response<Body>, EndpointError> {
+    let status = ROUTE_PLANNER
+        .as_ref()
+        .map(|planner| planner.get_status())
+        .unwrap_or_default();
+
+    let string = serde_json::to_string_pretty(&status)?;
+
+    Response::builder()
+        .header("Content-Type", "application/json")
+        .body(Body::from(string))
+        .map_err(|e| EndpointError::FailedMessage(e.to_string()))
+}
+
+#[tracing::instrument]
+pub async fn free_address(
+    Json(body): Json<FreeAddressBody>,
+) -> Result<Response<Body>, EndpointError> {
+    let planner = ROUTE_PLANNER
+        .as_ref()
+        .ok_or(EndpointError::RoutePlannerDisabled)?;
+
+    let address = IpAddr::from_str(&body.address)
+        .map_err(|_| EndpointError::InvalidIpAddress(body.address.clone()))?;
+
+    if planner.unban_ip(address) {
+        tracing::info!("RoutePlanner: Freed address {}", address);
+    }
+
+    Response::builder()
+        .status(StatusCode::NO_CONTENT)
+        .body(Body::empty())
+        .map_err(|e| EndpointError::FailedMessage(e.to_string()))
+}
+
+pub async fn free_all() -> Result<Response<Body>, EndpointError> {
+    let planner = ROUTE_PLANNER
+        .as_ref()
+        .ok_or(EndpointError::RoutePlannerDisabled)?;
+
+    planner.unban_all();
+
+    Response::builder()
+        .status(StatusCode::NO_CONTENT)
+        .body(Body::empty())
+        .map_err(|e| EndpointError::FailedMessage(e.to_string()))
+}
@@ -3,12 +3,11 @@ use super::*;
 use crate::models::{ApiTrack, ApiTrackInfo, ApiTrackPlaylist, ApiTrackResult, Empty};
 use crate::util::encoder::encode_track;
 use crate::util::errors::ResolverError;
-use crate::util::source::{Query, Source};
+use crate::util::source::{Playable, Query, Source};
 use crate::util::url::is_url;
 use async_trait::async_trait;
 use regex::Regex;
 use reqwest::Client;
-use songbird::input::Input;
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::Mutex;
@@ -419,7 +418,7 @@ impl AmazonMusic {
                     return Ok(Some(ApiTrackResult::Track(ApiTrack {
                         encoded: encode_track(selected)?,
                         info: selected.clone(),
-                        plugin_info: Empty,
+                        plugin_info: Default::default(),
                         user_data: None,
                     })));
                 }
@@ -428,7 +427,7 @@ impl AmazonMusic {
                 return Ok(Some(ApiTrackResult::Track(ApiTrack {
                     encoded: encode_track(&tracks[0])?,
                     info: tracks[0].clone(),
-                    plugin_info: Empty,
+                    plugin_info: Default::default(),
                     user_data: None,
                 })));
             }
@@ -443,7 +442,7 @@ impl AmazonMusic {
                     .map(|info| ApiTrack {
                         encoded: encode_track(&info).unwrap_or_default(),
                         info,
-                        plugin_info: Empty,
+                        plugin_info: Default::default(),
                         user_data: None,
                     })
                     .collect(),
@@ -487,7 +486,7 @@ impl AmazonMusic {
             return Ok(Some(ApiTrackResult::Track(ApiTrack {
                 encoded: encode_track(&track_info)?,
                 info: track_info,
-                plugin_info: Empty,
+                plugin_info: Default::default(),
                 user_data: None,
             })));
         }
@@ -786,7 +785,7 @@ impl AmazonMusic {
                 .map(|info| ApiTrack {
                     encoded: encode_track(&info).unwrap_or_default(),
                     info,
-                    plugin_info: Empty,
+                    plugin_info: Default::default(),
                     user_data: None,
                 })
                 .collect(),
@@ -847,7 +846,7 @@ impl Source for AmazonMusic {
         }
     }
 
-    async fn make_playable(&self, track: ApiTrack) -> Result<Input, ResolverError> {
+    async fn make_playable(&self, track: ApiTrack) -> Result<Playable, ResolverError> {
         let query = format!("ytsearch:{} - {}", track.info.author, track.info.title);
         tracing::debug!("AmazonMusic: Searching YouTube for: {}", query);
 
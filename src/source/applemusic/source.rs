@@ -5,13 +5,12 @@ use crate::models::{
 };
 use crate::util::encoder::encode_track;
 use crate::util::errors::ResolverError;
-use crate::util::source::{Query, Source};
+use crate::util::source::{Playable, Query, Source};
 use crate::util::url::is_url;
 use async_trait::async_trait;
 use base64::{Engine as _, engine::general_purpose};
 use regex::Regex;
 use reqwest::Client;
-use songbird::input::Input;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
@@ -308,7 +307,7 @@ impl AppleMusic {
         Ok(ApiTrack {
             encoded: encode_track(&info)?,
             info,
-            plugin_info: Empty,
+            plugin_info: Default::default(),
             user_data: None,
         })
     }
@@ -660,7 +659,7 @@ impl Source for AppleMusic {
         }
     }
 
-    async fn make_playable(&self, track: ApiTrack) -> Result<Input, ResolverError> {
+    async fn make_playable(&self, track: ApiTrack) -> Result<Playable, ResolverError> {
         if crate::SOURCES.get("youtube").is_none() {
             tracing::warn!(
                 "AppleMusic needs YouTube source for playback fallbacks, but it is not available"
@@ -17,7 +17,7 @@ use crate::models::{
 use crate::util::encoder::encode_track;
 use crate::util::errors::ResolverError;
 use crate::util::source::Query;
-use crate::util::source::Source;
+use crate::util::source::{Playable, Source};
 use crate::util::url::is_url;
 use async_trait::async_trait;
 use regex::Regex;
@@ -183,7 +183,7 @@ impl Source for Deezer {
         }
     }
 
-    async fn make_playable(&self, track: ApiTrack) -> Result<Input, ResolverError> {
+    async fn make_playable(&self, track: ApiTrack) -> Result<Playable, ResolverError> {
         let tokens = self.get_token().await?;
 
         // Use song.getListData (like the JS source) to get track token
@@ -290,7 +290,22 @@ impl Source for Deezer {
 
         let input = Input::Live(LiveInput::Raw(stream.create_async().await?), None);
 
-        Ok(input)
+        Ok(input.into())
+    }
+
+    fn related_query(&self, track: &ApiTrack) -> Option<String> {
+        if track.info.source_name == self.get_name() {
+            return Some(format!(
+                "{}{}",
+                self.search_prefixes.2, track.info.identifier
+            ));
+        }
+
+        // tracks from other sources are searched first by get_recommendations
+        Some(format!(
+            "{}{} {}",
+            self.search_prefixes.2, track.info.title, track.info.author
+        ))
     }
 }
 
@@ -703,7 +718,7 @@ impl Deezer {
         Ok(ApiTrack {
             encoded: encode_track(&info)?,
             info,
-            plugin_info: Empty,
+            plugin_info: Default::default(),
             user_data: None,
         })
     }
@@ -731,7 +746,7 @@ impl Deezer {
         Ok(ApiTrack {
             encoded: encode_track(&info)?,
             info,
-            plugin_info: Empty,
+            plugin_info: Default::default(),
             user_data: None,
         })
     }
@@ -761,7 +776,7 @@ impl Deezer {
         Ok(ApiTrack {
             encoded: encode_track(&info)?,
             info,
-            plugin_info: Empty,
+            plugin_info: Default::default(),
             user_data: None,
         })
     }
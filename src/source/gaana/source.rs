@@ -3,7 +3,7 @@ use super::model::*;
 use crate::models::{ApiTrack, ApiTrackInfo, ApiTrackResult};
 use crate::util::encoder::encode_track;
 use crate::util::errors::ResolverError;
-use crate::util::source::{Query, Source};
+use crate::util::source::{Playable, Query, Source};
 use crate::util::url::is_url;
 use crate::playback::hls::handler::start_hls_stream;
 use async_trait::async_trait;
@@ -89,7 +89,7 @@ impl Source for Gaana {
         }
     }
 
-    async fn make_playable(&self, track: ApiTrack) -> Result<Input, ResolverError> {
+    async fn make_playable(&self, track: ApiTrack) -> Result<Playable, ResolverError> {
         let track_id = &track.info.identifier;
 
         tracing::info!("Attempting to get stream URL for Gaana track: {}", track_id);
@@ -105,11 +105,11 @@ impl Source for Gaana {
         if stream_info.protocol == "hls" {
             tracing::info!("Using HLS handler for Gaana track: {}", track_id);
             let input = start_hls_stream(stream_info.url, self.get_client()).await;
-            return Ok(input);
+            return Ok(input.into());
         }
 
         // Direct HTTP stream
-        Ok(Input::from(HttpRequest::new(self.get_client(), stream_info.url)))
+        Ok(Input::from(HttpRequest::new(self.get_client(), stream_info.url)).into())
     }
 }
 
@@ -366,7 +366,7 @@ impl Gaana {
         Some(ApiTrack {
             encoded: encode_track(&info).ok()?,
             info,
-            plugin_info: crate::models::Empty, user_data: None
+            plugin_info: Default::default(), user_data: None
         })
     }
 
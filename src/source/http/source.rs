@@ -1,9 +1,9 @@
-use crate::models::{ApiTrack, ApiTrackInfo, ApiTrackResult, Empty};
+use crate::models::{ApiTrack, ApiTrackInfo, ApiTrackResult};
 use crate::util::encoder::encode_track;
 use crate::util::errors::ResolverError;
 use crate::util::seek::SeekableSource;
 use crate::util::source::Query;
-use crate::util::source::Source;
+use crate::util::source::{Playable, Source};
 use crate::util::url::is_url;
 use async_trait::async_trait;
 use reqwest::Client;
@@ -76,14 +76,14 @@ impl Source for Http {
         let track = ApiTrack {
             encoded: encode_track(&info)?,
             info,
-            plugin_info: Empty,
+            plugin_info: Default::default(),
             user_data: None,
         };
 
         Ok(Some(ApiTrackResult::Track(track)))
     }
 
-    async fn make_playable(&self, track: ApiTrack) -> Result<Input, ResolverError> {
+    async fn make_playable(&self, track: ApiTrack) -> Result<Playable, ResolverError> {
         let url = track
             .info
             .uri
@@ -97,7 +97,8 @@ impl Source for Http {
         Ok(Input::Live(
             songbird::input::LiveInput::Raw(seekable.into_audio_stream(stream.hint)),
             None,
-        ))
+        )
+        .into())
     }
 }
 
@@ -7,7 +7,7 @@ use crate::CONFIG;
 use crate::models::{ApiTrack, ApiTrackInfo, ApiTrackResult};
 use crate::util::encoder::encode_track;
 use crate::util::errors::ResolverError;
-use crate::util::source::{Query, Source};
+use crate::util::source::{Playable, Query, Source};
 use crate::util::url::is_url;
 use async_trait::async_trait;
 use base64::prelude::*;
@@ -89,9 +89,15 @@ impl Source for JioSaavn {
         }
     }
 
-    async fn make_playable(&self, track: ApiTrack) -> Result<Input, ResolverError> {
+    async fn make_playable(&self, track: ApiTrack) -> Result<Playable, ResolverError> {
         let url = self.get_stream_url(&track.info.identifier).await?;
-        Ok(Input::from(HttpRequest::new(self.get_client(), url)))
+        Ok(Input::from(HttpRequest::new(self.get_client(), url)).into())
+    }
+
+    fn related_query(&self, track: &ApiTrack) -> Option<String> {
+        // recommendations are looked up by JioSaavn's own track id
+        (track.info.source_name == self.get_name())
+            .then(|| format!("{}{}", self.recommendation_prefix, track.info.identifier))
     }
 }
 
@@ -395,7 +401,7 @@ impl JioSaavn {
         Ok(ApiTrack {
             encoded: encode_track(&info)?,
             info,
-            plugin_info: crate::models::Empty,
+            plugin_info: Default::default(),
             user_data: None,
         })
     }
@@ -1,11 +1,10 @@
 use async_trait::async_trait;
 use reqwest::Client;
 use serde_json::Value;
-use songbird::input::Input;
 
 use crate::models::{ApiTrack, ApiTrackResult};
 use crate::util::errors::ResolverError;
-use crate::util::source::{Query, Source};
+use crate::util::source::{Playable, Query, Source};
 
 pub struct Songlink {
     http: Client,
@@ -114,7 +113,7 @@ impl Source for Songlink {
         Ok(None)
     }
 
-    async fn make_playable(&self, _track: ApiTrack) -> Result<Input, ResolverError> {
+    async fn make_playable(&self, _track: ApiTrack) -> Result<Playable, ResolverError> {
         Err(ResolverError::Custom(
             "Songlink source does not support direct playback".to_string(),
         ))
@@ -6,7 +6,7 @@ use crate::source::soundcloud::model::*;
 use crate::source::soundcloud::{BASE_URL, BATCH_SIZE, SOUNDCLOUD_URL};
 use crate::util::encoder::encode_track;
 use crate::util::errors::ResolverError;
-use crate::util::source::{Query, Source};
+use crate::util::source::{Playable, Query, Source};
 use async_trait::async_trait;
 use regex::Regex;
 use reqwest::Client;
@@ -335,7 +335,7 @@ impl SoundCloud {
         ApiTrack {
             encoded: encode_track(&info).unwrap_or_default(),
             info,
-            plugin_info: Empty,
+            plugin_info: Default::default(),
             user_data: None,
         }
     }
@@ -358,7 +358,7 @@ impl SoundCloud {
         ApiTrack {
             encoded: encode_track(&info).unwrap_or_default(),
             info,
-            plugin_info: Empty,
+            plugin_info: Default::default(),
             user_data: None,
         }
     }
@@ -391,7 +391,7 @@ impl SoundCloud {
                 String::new()
             }),
             info,
-            plugin_info: Empty,
+            plugin_info: Default::default(),
             user_data: None,
         }
     }
@@ -730,7 +730,7 @@ impl Source for SoundCloud {
         }
     }
 
-    async fn make_playable(&self, track: ApiTrack) -> Result<Input, ResolverError> {
+    async fn make_playable(&self, track: ApiTrack) -> Result<Playable, ResolverError> {
         let client_id = self.get_client_id().await?;
         let tracks_url = format!("{}/tracks/{}", BASE_URL, track.info.identifier);
         let user_agent = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/122.0.0.0 Safari/537.36";
@@ -752,9 +752,9 @@ impl Source for SoundCloud {
         let track_data: Track = response.json().await?;
         let (url, protocol, _format) = self.select_transcoding(&track_data).await?;
         if protocol == "hls" {
-            Ok(start_hls_stream(url, self.client.clone()).await)
+            Ok(start_hls_stream(url, self.client.clone()).await.into())
         } else {
-            Ok(Input::from(HttpRequest::new(self.client.clone(), url)))
+            Ok(Input::from(HttpRequest::new(self.client.clone(), url)).into())
         }
     }
 }
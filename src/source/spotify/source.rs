@@ -1,12 +1,11 @@
 use crate::CONFIG;
 use crate::models::{ApiTrack, ApiTrackInfo, ApiTrackResult};
 use crate::util::errors::ResolverError;
-use crate::util::source::{Query, Source};
+use crate::util::source::{Playable, Query, Source};
 use async_trait::async_trait;
 use base64::{Engine as _, engine::general_purpose};
 use reqwest::Client;
 use serde_json::Value;
-use songbird::input::Input;
 use std::sync::Arc;
 use std::time::SystemTime;
 use tokio::sync::Mutex;
@@ -137,7 +136,7 @@ impl Spotify {
         Some(ApiTrack {
             encoded: crate::util::encoder::encode_track(&info).ok()?,
             info,
-            plugin_info: crate::models::Empty,
+            plugin_info: Default::default(),
             user_data: None,
         })
     }
@@ -226,7 +225,7 @@ impl Spotify {
                     tracks.push(ApiTrack {
                         encoded,
                         info,
-                        plugin_info: crate::models::Empty,
+                        plugin_info: Default::default(),
                         user_data: None,
                     });
                 }
@@ -381,7 +380,7 @@ impl Source for Spotify {
         Ok(None)
     }
 
-    async fn make_playable(&self, _track: ApiTrack) -> Result<Input, ResolverError> {
+    async fn make_playable(&self, _track: ApiTrack) -> Result<Playable, ResolverError> {
         // IMPORTANT: We need to implement this "bridge" logic.
         // Since we can't easily access the Youtube instance from here without circular deps or global map lookup...
         // We CAN access `crate::SOURCES` global map!
@@ -15,6 +15,9 @@ use super::sabr::Sabr;
 use crate::CONFIG;
 use crate::util::errors::ResolverError;
 
+/// Loudness YouTube normalizes playback to, in LUFS
+const YOUTUBE_REFERENCE_LUFS: f64 = -14.0;
+
 pub struct YouTubeManager {
     pub(crate) http: Client,
     pub(crate) api: InnertubeApi,
@@ -268,10 +271,11 @@ impl YouTubeManager {
         Err(last_error)
     }
 
+    /// Stream URL of `video_id` with the client and headers to fetch it, and the loudness YouTube measured in LUFS
     pub async fn make_playable(
         &self,
         video_id: &str,
-    ) -> Result<(String, Client, reqwest::header::HeaderMap), ResolverError> {
+    ) -> Result<(String, Client, reqwest::header::HeaderMap, Option<f64>), ResolverError> {
         let mut last_error =
             ResolverError::Custom("No clients configured for playback".to_string());
 
@@ -425,6 +429,14 @@ impl YouTubeManager {
                 continue;
             };
 
+            // loudnessDb is relative to the level YouTube normalizes to
+            let loudness = player_response
+                .get("playerConfig")
+                .and_then(|c| c.get("audioConfig"))
+                .and_then(|c| c.get("loudnessDb"))
+                .and_then(|l| l.as_f64())
+                .map(|loudness_db| YOUTUBE_REFERENCE_LUFS + loudness_db);
+
             let mut formats = Vec::new();
             if let Some(f) = streaming_data.get("formats").and_then(|v| v.as_array()) {
                 formats.extend(f.iter());
@@ -436,15 +448,7 @@ impl YouTubeManager {
                 formats.extend(f.iter());
             }
 
-            let audio_format = formats
-                .iter()
-                .filter(|f| {
-                    f.get("mimeType")
-                        .and_then(|m| m.as_str())
-                        .map(|s| s.starts_with("audio"))
-                        .unwrap_or(false)
-                })
-                .max_by_key(|f| f.get("bitrate").and_then(|b| b.as_u64()).unwrap_or(0));
+            let audio_format = select_audio_format(&formats);
 
             if let Some(fmt) = audio_format {
                 if let Some(url) = fmt.get("url").and_then(|u| u.as_str()) {
@@ -516,7 +520,7 @@ impl YouTubeManager {
                         ),
                         _ => {}
                     }
-                    return Ok((final_url.clone(), stream_client, stream_headers));
+                    return Ok((final_url.clone(), stream_client, stream_headers, loudness));
                 } else if let Some(sig_cipher) = fmt.get("signatureCipher").and_then(|s| s.as_str())
                 {
                     debug!(
@@ -590,7 +594,12 @@ impl YouTubeManager {
                                     ),
                                     _ => {}
                                 }
-                                return Ok((final_url.clone(), stream_client, stream_headers));
+                                return Ok((
+                                    final_url.clone(),
+                                    stream_client,
+                                    stream_headers,
+                                    loudness,
+                                ));
                             }
                             Err(e) => {
                                 warn!("Cipher resolution failed for {}: {:?}", client.name(), e);
@@ -709,6 +718,71 @@ impl YouTubeManager {
 
         Ok((playlist_name, all_videos))
     }
+
+    /// Loads the watch page of `video_id` with its radio mix, which lists related videos
+    pub async fn load_related(&self, video_id: &str) -> Result<Value, ResolverError> {
+        let visitor_data = self.sabr.lock().await.get_visitor_data();
+        let oauth_token = self.oauth.lock().await.get_access_token();
+
+        let (http_client, bound_ip) = crate::get_client();
+
+        let client_name = self
+            .search_clients
+            .first()
+            .cloned()
+            .unwrap_or_else(|| "Web".to_string());
+        let client = self
+            .get_innertube_client(&client_name)
+            .ok_or_else(|| ResolverError::Custom("No search client available".to_string()))?;
+
+        let mix_id = format!("RD{}", video_id);
+
+        self.api
+            .next(
+                Some(video_id),
+                Some(&mix_id),
+                None,
+                client.as_ref(),
+                visitor_data.as_deref(),
+                oauth_token.as_deref(),
+                &http_client,
+                bound_ip,
+            )
+            .await
+    }
+}
+
+/// Picks the configured target itag if present, else the best allowed audio format.
+/// Opus is preferred so songbird can pass it through without decoding when no filters are active
+fn select_audio_format<'a>(formats: &[&'a Value]) -> Option<&'a Value> {
+    let youtube_config = CONFIG.youtube_config.as_ref();
+    let itag = |f: &Value| f.get("itag").and_then(|i| i.as_u64()).unwrap_or(0) as u32;
+
+    let audio_formats = formats.iter().copied().filter(|f| {
+        f.get("mimeType")
+            .and_then(|m| m.as_str())
+            .map(|s| s.starts_with("audio"))
+            .unwrap_or(false)
+    });
+
+    if let Some(target) = youtube_config.and_then(|c| c.target_itag) {
+        if let Some(format) = audio_formats.clone().find(|f| itag(f) == target) {
+            return Some(format);
+        }
+    }
+
+    let allowed = youtube_config.and_then(|c| c.allow_itag.as_ref());
+
+    audio_formats
+        .filter(|f| allowed.is_none_or(|allowed| allowed.contains(&itag(f))))
+        .max_by_key(|f| {
+            let opus = f
+                .get("mimeType")
+                .and_then(|m| m.as_str())
+                .is_some_and(|s| s.contains("opus"));
+            let bitrate = f.get("bitrate").and_then(|b| b.as_u64()).unwrap_or(0);
+            (opus, bitrate)
+        })
 }
 
 fn extract_playlist_name(json: &Value) -> Option<String> {
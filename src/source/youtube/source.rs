@@ -8,7 +8,7 @@ use tracing::{debug, warn};
 use super::manager::YouTubeManager;
 use crate::models::{ApiTrack, ApiTrackInfo, ApiTrackResult};
 use crate::util::errors::ResolverError;
-use crate::util::source::{Query, Source};
+use crate::util::source::{Playable, Query, Source};
 
 pub struct Youtube {
     manager: Arc<YouTubeManager>,
@@ -205,7 +205,7 @@ impl Youtube {
         Some(ApiTrack {
             encoded: crate::util::encoder::encode_track(&info).ok()?,
             info,
-            plugin_info: crate::models::Empty,
+            plugin_info: Default::default(),
             user_data: None,
         })
     }
@@ -364,7 +364,7 @@ impl Source for Youtube {
                         return Ok(Some(ApiTrackResult::Track(ApiTrack {
                             encoded: crate::util::encoder::encode_track(&track_info)?,
                             info: track_info,
-                            plugin_info: crate::models::Empty,
+                            plugin_info: Default::default(),
                             user_data: None,
                         })));
                     }
@@ -374,16 +374,75 @@ impl Source for Youtube {
         }
     }
 
-    async fn make_playable(&self, track: ApiTrack) -> Result<Input, ResolverError> {
-        let (stream_url, client, headers) =
+    async fn make_playable(&self, track: ApiTrack) -> Result<Playable, ResolverError> {
+        let (stream_url, client, headers, loudness) =
             self.manager.make_playable(&track.info.identifier).await?;
-        Ok(Input::from(
-            crate::source::youtube::stream::YoutubeHttpStream::new(client, stream_url, headers),
-        ))
+        Ok(Playable {
+            input: Input::from(crate::source::youtube::stream::YoutubeHttpStream::new(
+                client, stream_url, headers,
+            )),
+            loudness,
+        })
+    }
+
+    // only reachable from autoplay, a seed from another source still needs a search to find its video
+    async fn related(&self, track: &ApiTrack) -> Result<Option<ApiTrackResult>, ResolverError> {
+        if track.info.source_name == self.get_name() {
+            return self.resolve_related(&track.info.identifier).await;
+        }
+
+        let allow_search = crate::CONFIG
+            .youtube_config
+            .as_ref()
+            .and_then(|c| c.allow_search)
+            .unwrap_or(true);
+        if !allow_search {
+            debug!(
+                "YouTube: Not searching for a video related to {}, allowSearch is false",
+                track.info.identifier
+            );
+            return Ok(None);
+        }
+
+        self.resolve_related(&format!("{} {}", track.info.title, track.info.author))
+            .await
     }
 }
 
 impl Youtube {
+    /// Videos from the radio mix of `seed`, a video id or a search term whose first result seeds it
+    async fn resolve_related(&self, seed: &str) -> Result<Option<ApiTrackResult>, ResolverError> {
+        let is_video_id = seed.len() == 11
+            && seed
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+
+        let video_id = if is_video_id {
+            seed.to_string()
+        } else {
+            let mut tracks = Vec::new();
+            self.extract_video_renderers(&self.manager.search(seed).await?, &mut tracks);
+            match tracks.into_iter().next() {
+                Some(track) => track.info.identifier,
+                None => return Ok(Some(ApiTrackResult::Empty(None))),
+            }
+        };
+
+        debug!("YouTube: Loading related videos for {}", video_id);
+
+        let res = self.manager.load_related(&video_id).await?;
+
+        let mut tracks = Vec::new();
+        self.extract_video_renderers(&res, &mut tracks);
+        tracks.retain(|track| track.info.identifier != video_id);
+
+        if tracks.is_empty() {
+            return Ok(Some(ApiTrackResult::Empty(None)));
+        }
+
+        Ok(Some(ApiTrackResult::Search(tracks)))
+    }
+
     async fn resolve_playlist(
         &self,
         playlist_id: &str,
@@ -480,7 +539,7 @@ impl Youtube {
         Some(ApiTrack {
             encoded: crate::util::encoder::encode_track(&info).ok()?,
             info,
-            plugin_info: crate::models::Empty,
+            plugin_info: Default::default(),
             user_data: None,
         })
     }
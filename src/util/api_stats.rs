@@ -1,4 +1,5 @@
 use crate::models::{ApiCpu, ApiFrameStats, ApiMemory, ApiStats};
+use crate::util::connection::CONNECTION_MONITOR;
 use crate::util::frame_counter::EXPECTED_FRAMES_PER_MIN;
 use crate::voice::player::{GetFrameCounter, IsActive};
 use crate::ws::client::GetAllPlayers;
@@ -105,5 +106,6 @@ pub async fn get_stats() -> ApiStats {
             lavalink_load: process_cpu,
         },
         frame_stats,
+        connection: CONNECTION_MONITOR.status(),
     }
 }
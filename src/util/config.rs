@@ -130,9 +130,12 @@ pub struct LoggingFileConfig {
 #[serde(rename_all = "camelCase")]
 pub struct LoggingConfig {
     pub level: String,
+    pub format: Option<String>,
     pub file: LoggingFileConfig,
 }
 
+/// Latency limits in milliseconds, unlike NodeLink's throughput values a higher number is worse.
+/// Up to `average` is good, up to `bad` is average and anything slower is bad, so `bad` must be above `average`
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ConnectionThresholds {
@@ -146,6 +149,7 @@ pub struct ConnectionConfig {
     pub log_all_checks: bool,
     pub interval: u64,
     pub timeout: u64,
+    pub target: Option<String>,
     pub thresholds: ConnectionThresholds,
 }
 
@@ -201,6 +205,32 @@ pub struct AudioFading {
     pub ducking: AudioDucking,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioLimiter {
+    pub enabled: bool,
+    /// Level in dBFS where the limiter starts bending peaks
+    pub threshold: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioNormalization {
+    /// Whether players normalize loudness unless their filters say otherwise
+    pub enabled: bool,
+    /// Integrated loudness to normalize to, in LUFS
+    pub target: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioSkipSilence {
+    /// Whether players cut leading and trailing silence unless their filters say otherwise
+    pub enabled: bool,
+    /// Level in dBFS below which audio counts as silence
+    pub threshold: Option<f64>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AudioConfig {
@@ -208,6 +238,20 @@ pub struct AudioConfig {
     pub encryption: String,
     pub resampling_quality: String,
     pub fading: AudioFading,
+    pub limiter: Option<AudioLimiter>,
+    pub normalization: Option<AudioNormalization>,
+    pub skip_silence: Option<AudioSkipSilence>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoplayConfig {
+    /// How long a finished player waits for the client before picking a track itself
+    pub grace_period_ms: Option<u64>,
+    /// Source asked for related tracks when the finished track's own source has none
+    pub fallback_source: Option<String>,
+    /// Number of recently played tracks autoplay will not pick again
+    pub history_size: Option<usize>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -337,6 +381,7 @@ pub struct Config {
     pub connection: Option<ConnectionConfig>,
     pub filters: Option<FiltersConfig>,
     pub audio: Option<AudioConfig>,
+    pub autoplay: Option<AutoplayConfig>,
     pub route_planner: Option<RoutePlannerConfig>,
     pub rate_limit: Option<RateLimitConfig>,
     pub dos_protection: Option<DosProtectionConfig>,
@@ -362,6 +407,18 @@ impl Default for Config {
 impl Config {
     pub fn new() -> Self {
         let config = fs::read_to_string("./config.json").expect("Missing ./config.json");
-        serde_json::from_str::<Config>(&config).unwrap()
+        let config = serde_json::from_str::<Config>(&config).unwrap();
+
+        if let Some(connection) = config.connection.as_ref() {
+            let thresholds = &connection.thresholds;
+            assert!(
+                thresholds.bad > thresholds.average,
+                "connection.thresholds are latencies in ms and bad ({}) must be above average ({})",
+                thresholds.bad,
+                thresholds.average
+            );
+        }
+
+        config
     }
 }
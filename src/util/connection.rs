@@ -0,0 +1,141 @@
+use crate::CLIENTS;
+use crate::models::{ApiConnectionQuality, ApiConnectionStatus};
+use crate::util::config::ConnectionConfig;
+use crate::voice::player::GetApiPlayerInfo;
+use crate::ws::client::GetAllPlayers;
+use std::sync::{LazyLock, RwLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+/// Used when no player is connected to a voice server and no target is configured
+const DEFAULT_TARGET: &str = "discord.com:443";
+
+/// Amount of connections opened per check, the latency reported is their average
+const SAMPLES: usize = 3;
+
+pub static CONNECTION_MONITOR: LazyLock<ConnectionMonitor> =
+    LazyLock::new(ConnectionMonitor::default);
+
+/// Keeps the result of the last outbound connection quality check
+#[derive(Default)]
+pub struct ConnectionMonitor {
+    last: RwLock<Option<ApiConnectionStatus>>,
+}
+
+impl ConnectionMonitor {
+    pub fn status(&self) -> Option<ApiConnectionStatus> {
+        self.last.read().ok().and_then(|last| last.clone())
+    }
+
+    /// Measures the latency to the target then classifies it using the configured thresholds (in ms)
+    pub async fn check(&self, config: &ConnectionConfig) -> ApiConnectionStatus {
+        let target = resolve_target(config).await;
+        let limit = Duration::from_millis(config.timeout);
+
+        let mut total = Duration::ZERO;
+        let mut successful = 0;
+
+        for _ in 0..SAMPLES {
+            let start = Instant::now();
+
+            match timeout(limit, TcpStream::connect(target.as_str())).await {
+                Ok(Ok(_)) => {
+                    total += start.elapsed();
+                    successful += 1;
+                }
+                Ok(Err(error)) => {
+                    tracing::debug!("Connection check to [{}] failed: {}", target, error);
+                }
+                Err(_) => {
+                    tracing::debug!("Connection check to [{}] timed out", target);
+                }
+            }
+        }
+
+        let latency = (successful > 0).then(|| total.as_secs_f64() * 1000.0 / successful as f64);
+
+        // a single failed sample is enough to consider the uplink degraded
+        let status = match latency {
+            Some(latency) if successful == SAMPLES && latency <= config.thresholds.average => {
+                ApiConnectionQuality::Good
+            }
+            Some(latency) if latency <= config.thresholds.bad => ApiConnectionQuality::Average,
+            _ => ApiConnectionQuality::Bad,
+        };
+
+        let result = ApiConnectionStatus {
+            status,
+            latency,
+            target,
+            checked_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+        };
+
+        let previous = self
+            .last
+            .write()
+            .ok()
+            .and_then(|mut last| last.replace(result.clone()));
+
+        let changed = previous.is_none_or(|previous| previous.status != result.status);
+
+        if changed && result.status != ApiConnectionQuality::Good {
+            tracing::warn!(
+                "Connection quality to [{}] is now {:?} (latency: {:?}ms)",
+                result.target,
+                result.status,
+                result.latency
+            );
+        } else if changed || config.log_all_checks {
+            tracing::info!(
+                "Connection quality to [{}] is {:?} (latency: {:?}ms)",
+                result.target,
+                result.status,
+                result.latency
+            );
+        }
+
+        result
+    }
+}
+
+/// Prefers the voice server of a connected player, then the configured target
+async fn resolve_target(config: &ConnectionConfig) -> String {
+    if let Some(target) = config.target.as_ref() {
+        return with_port(target);
+    }
+
+    let clients: Vec<_> = CLIENTS.iter().map(|client| client.clone()).collect();
+
+    for client in clients {
+        let Ok(players) = client.ask(GetAllPlayers).await else {
+            continue;
+        };
+
+        for (_, player) in players {
+            let Ok(info) = player.ask(GetApiPlayerInfo).await else {
+                continue;
+            };
+
+            if info.state.connected && !info.voice.endpoint.is_empty() {
+                return with_port(&info.voice.endpoint);
+            }
+        }
+    }
+
+    DEFAULT_TARGET.to_string()
+}
+
+fn with_port(target: &str) -> String {
+    if target
+        .rsplit_once(':')
+        .is_some_and(|(_, port)| port.parse::<u16>().is_ok())
+    {
+        target.to_string()
+    } else {
+        format!("{target}:443")
+    }
+}
@@ -136,6 +136,8 @@ pub enum Base64EncodeError {
 pub enum EndpointError {
     #[error("Unauthorized")]
     Unauthorized,
+    #[error("Authentication required")]
+    AuthenticationRequired(&'static str),
     #[error("No websocket client found for the session id provided")]
     NoWebsocketClientFound,
     #[error("No player found for the guild id provided")]
@@ -150,6 +152,16 @@ pub enum EndpointError {
     UnprocessableEntity(&'static str),
     #[error("Invalid IP address: {0}")]
     InvalidIpAddress(String),
+    #[error("Can't access disabled route planner")]
+    RoutePlannerDisabled,
+    #[error("The following filters are disabled on this node: {0}")]
+    DisabledFilters(String),
+    #[error("Ducking is disabled on this node")]
+    DuckingDisabled,
+    #[error("Too many requests, retry after {0} second(s)")]
+    TooManyRequests(u64),
+    #[error("Temporarily blocked due to excessive requests, retry after {0} second(s)")]
+    TemporarilyBlocked(u64),
     #[error("Failed to send a message to a task: {0}")]
     FailedMessage(String),
     #[error(transparent)]
@@ -197,6 +209,17 @@ impl IntoResponse for EndpointError {
             self
         );
 
+        let retry_after = match &self {
+            EndpointError::TooManyRequests(seconds) => Some(*seconds),
+            EndpointError::TemporarilyBlocked(seconds) => Some(*seconds),
+            _ => None,
+        };
+
+        let challenge = match &self {
+            EndpointError::AuthenticationRequired(challenge) => Some(*challenge),
+            _ => None,
+        };
+
         let (status, message) = match self {
             EndpointError::MissingOption(_) => (StatusCode::BAD_REQUEST, self.to_string()),
             EndpointError::UnprocessableEntity(_) => {
@@ -244,7 +267,19 @@ impl IntoResponse for EndpointError {
                 (StatusCode::INTERNAL_SERVER_ERROR, actor_error.to_string())
             }
             EndpointError::InvalidIpAddress(_) => (StatusCode::BAD_REQUEST, self.to_string()),
+            EndpointError::RoutePlannerDisabled => {
+                (StatusCode::INTERNAL_SERVER_ERROR, self.to_string())
+            }
+            EndpointError::DisabledFilters(_) => (StatusCode::BAD_REQUEST, self.to_string()),
+            EndpointError::DuckingDisabled => (StatusCode::BAD_REQUEST, self.to_string()),
+            EndpointError::TooManyRequests(_) => (StatusCode::TOO_MANY_REQUESTS, self.to_string()),
+            EndpointError::TemporarilyBlocked(_) => {
+                (StatusCode::TOO_MANY_REQUESTS, self.to_string())
+            }
             EndpointError::Unauthorized => (StatusCode::FORBIDDEN, self.to_string()),
+            EndpointError::AuthenticationRequired(_) => {
+                (StatusCode::UNAUTHORIZED, self.to_string())
+            }
         };
 
         let timestamp = std::time::SystemTime::now()
@@ -260,10 +295,18 @@ impl IntoResponse for EndpointError {
             "path": ""
         });
 
-        Response::builder()
+        let mut builder = Response::builder()
             .status(status)
-            .header("Content-Type", "application/json")
-            .body(Body::from(error_body.to_string()))
-            .unwrap()
+            .header("Content-Type", "application/json");
+
+        if let Some(seconds) = retry_after {
+            builder = builder.header("Retry-After", seconds.to_string());
+        }
+
+        if let Some(challenge) = challenge {
+            builder = builder.header("WWW-Authenticate", challenge);
+        }
+
+        builder.body(Body::from(error_body.to_string())).unwrap()
     }
 }
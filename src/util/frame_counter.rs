@@ -11,6 +11,8 @@ pub struct FrameCounter {
     last_track_started: AtomicI64,
     last_track_ended: AtomicI64,
     last_counted_at: AtomicI64,
+    last_position: AtomicU64,
+    last_progress_at: AtomicI64,
 }
 
 const ACCEPTABLE_TRACK_SWITCH_MS: i64 = 100;
@@ -27,6 +29,8 @@ impl FrameCounter {
             last_track_started: AtomicI64::new(i64::MAX / 2),
             last_track_ended: AtomicI64::new(i64::MAX),
             last_counted_at: AtomicI64::new(0),
+            last_position: AtomicU64::new(0),
+            last_progress_at: AtomicI64::new(0),
         }
     }
 
@@ -72,8 +76,29 @@ impl FrameCounter {
         }
     }
 
+    /// Records the track position and returns how long (in ms) it went without progressing
+    pub fn on_position(&self, position: u64, playing: bool) -> u64 {
+        let now = Self::now_ms();
+        let last = self.last_position.swap(position, Ordering::Relaxed);
+
+        if !playing || position != last {
+            self.last_progress_at.store(now, Ordering::Relaxed);
+            return 0;
+        }
+
+        (now - self.last_progress_at.load(Ordering::Relaxed)).max(0) as u64
+    }
+
+    /// Resets the stuck detection, used after a stuck track was reported
+    pub fn reset_progress(&self) {
+        self.last_progress_at
+            .store(Self::now_ms(), Ordering::Relaxed);
+    }
+
     pub fn on_track_start(&self) {
         let now = Self::now_ms();
+        self.last_position.store(0, Ordering::Relaxed);
+        self.last_progress_at.store(now, Ordering::Relaxed);
         self.last_track_started.store(now, Ordering::Relaxed);
         let ended = self.last_track_ended.load(Ordering::Relaxed);
         let playing_since = self.playing_since.load(Ordering::Relaxed);
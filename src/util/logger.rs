@@ -0,0 +1,168 @@
+use crate::util::config::LoggingConfig;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer, Registry, fmt};
+
+const FILE_PREFIX: &str = "ibuki";
+const FILE_SUFFIX: &str = "log";
+
+type BoxedLayer = Box<dyn Layer<Registry> + Send + Sync>;
+
+fn is_json(config: Option<&LoggingConfig>) -> bool {
+    config
+        .and_then(|config| config.format.as_deref())
+        .is_some_and(|format| format.eq_ignore_ascii_case("json"))
+}
+
+/// Builds the level filter, RUST_LOG takes priority over the configured level.
+/// Both accept per module overrides, e.g. `info,ibuki::voice=debug`
+fn filter(config: Option<&LoggingConfig>) -> EnvFilter {
+    if let Ok(filter) = EnvFilter::try_from_default_env() {
+        return filter;
+    }
+
+    let level = config
+        .map(|config| config.level.as_str())
+        .unwrap_or("debug");
+
+    EnvFilter::try_new(level).unwrap_or_else(|error| {
+        eprintln!("Invalid logging level [{level}] ({error}), falling back to info");
+        EnvFilter::new("info")
+    })
+}
+
+/// Installs the global logger, the returned guard flushes the file writer when dropped
+pub fn init(config: Option<&LoggingConfig>) -> Option<WorkerGuard> {
+    let json = is_json(config);
+
+    let stdout: BoxedLayer = if json {
+        fmt::layer()
+            .json()
+            .with_file(true)
+            .with_line_number(true)
+            .with_thread_ids(true)
+            .with_thread_names(true)
+            .with_target(true)
+            .boxed()
+    } else {
+        fmt::layer()
+            .pretty()
+            .with_file(true)
+            .with_line_number(true)
+            .with_thread_ids(true)
+            .with_thread_names(true)
+            .with_target(true)
+            .boxed()
+    };
+
+    let mut layers = vec![stdout];
+    let mut guard = None;
+
+    if let Some(file) = config
+        .map(|config| &config.file)
+        .filter(|file| file.enabled)
+    {
+        let rotation = match file.rotation.to_lowercase().as_str() {
+            "hourly" => Rotation::HOURLY,
+            _ => Rotation::DAILY,
+        };
+
+        match RollingFileAppender::builder()
+            .rotation(rotation)
+            .filename_prefix(FILE_PREFIX)
+            .filename_suffix(FILE_SUFFIX)
+            .build(&file.path)
+        {
+            Ok(appender) => {
+                let (writer, worker_guard) = tracing_appender::non_blocking(appender);
+
+                let layer: BoxedLayer = if json {
+                    fmt::layer()
+                        .json()
+                        .with_ansi(false)
+                        .with_file(true)
+                        .with_line_number(true)
+                        .with_target(true)
+                        .with_writer(writer)
+                        .boxed()
+                } else {
+                    fmt::layer()
+                        .with_ansi(false)
+                        .with_file(true)
+                        .with_line_number(true)
+                        .with_target(true)
+                        .with_writer(writer)
+                        .boxed()
+                };
+
+                layers.push(layer);
+                guard = Some(worker_guard);
+            }
+            Err(error) => {
+                eprintln!(
+                    "Failed to create the log directory [{}] ({error}), logging to stdout only",
+                    file.path
+                );
+            }
+        }
+    }
+
+    tracing_subscriber::registry()
+        .with(layers)
+        .with(filter(config))
+        .init();
+
+    // pruned once the subscriber is up so what it removes gets logged
+    if let Some(file) = config
+        .map(|config| &config.file)
+        .filter(|file| file.enabled)
+    {
+        prune(&file.path, file.ttl_days);
+    }
+
+    guard
+}
+
+/// Deletes rotated log files under `path` that were last written more than `ttl_days` ago
+pub fn prune(path: impl AsRef<Path>, ttl_days: u32) {
+    if ttl_days == 0 {
+        return;
+    }
+
+    let Ok(entries) = fs::read_dir(path) else {
+        return;
+    };
+
+    let ttl = Duration::from_secs(ttl_days as u64 * 24 * 60 * 60);
+    let now = SystemTime::now();
+
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+
+        if !name.starts_with(FILE_PREFIX) || !name.ends_with(FILE_SUFFIX) {
+            continue;
+        }
+
+        let expired = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+            .is_some_and(|age| age > ttl);
+
+        if !expired {
+            continue;
+        }
+
+        match fs::remove_file(entry.path()) {
+            Ok(_) => tracing::info!("Pruned old log file [{}]", name),
+            Err(error) => tracing::warn!("Failed to prune log file [{}] ({})", name, error),
+        }
+    }
+}
@@ -0,0 +1,267 @@
+use crate::models::ApiStats;
+use dashmap::DashMap;
+use std::fmt::Write;
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Upper bounds (in seconds) of the latency histogram buckets
+const LATENCY_BUCKETS: [f64; 9] = [0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+pub static SOURCE_METRICS: LazyLock<SourceMetrics> = LazyLock::new(SourceMetrics::default);
+
+#[derive(Default)]
+struct OperationStats {
+    calls: AtomicU64,
+    errors: AtomicU64,
+    buckets: [AtomicU64; LATENCY_BUCKETS.len()],
+    sum_micros: AtomicU64,
+}
+
+/// Per source call counters and latency histograms for resolve and make_playable
+#[derive(Default)]
+pub struct SourceMetrics {
+    operations: DashMap<(&'static str, &'static str), OperationStats>,
+}
+
+impl SourceMetrics {
+    pub fn record(
+        &self,
+        source: &'static str,
+        operation: &'static str,
+        elapsed: Duration,
+        ok: bool,
+    ) {
+        let stats = self.operations.entry((source, operation)).or_default();
+
+        stats.calls.fetch_add(1, Ordering::Relaxed);
+
+        if !ok {
+            stats.errors.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let seconds = elapsed.as_secs_f64();
+
+        for (index, bound) in LATENCY_BUCKETS.iter().enumerate() {
+            if seconds <= *bound {
+                stats.buckets[index].fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        stats
+            .sum_micros
+            .fetch_add(elapsed.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    fn render(&self, out: &mut String) {
+        let mut entries: Vec<_> = self.operations.iter().collect();
+        entries.sort_by_key(|entry| *entry.key());
+
+        header(
+            out,
+            "ibuki_source_requests_total",
+            "counter",
+            "Total calls made to a source",
+        );
+        for entry in &entries {
+            let (source, operation) = entry.key();
+            let _ = writeln!(
+                out,
+                "ibuki_source_requests_total{{source=\"{source}\",operation=\"{operation}\"}} {}",
+                entry.calls.load(Ordering::Relaxed)
+            );
+        }
+
+        header(
+            out,
+            "ibuki_source_errors_total",
+            "counter",
+            "Total failed calls made to a source",
+        );
+        for entry in &entries {
+            let (source, operation) = entry.key();
+            let _ = writeln!(
+                out,
+                "ibuki_source_errors_total{{source=\"{source}\",operation=\"{operation}\"}} {}",
+                entry.errors.load(Ordering::Relaxed)
+            );
+        }
+
+        header(
+            out,
+            "ibuki_source_duration_seconds",
+            "histogram",
+            "Latency of calls made to a source",
+        );
+        for entry in &entries {
+            let (source, operation) = entry.key();
+            for (index, bound) in LATENCY_BUCKETS.iter().enumerate() {
+                let _ = writeln!(
+                    out,
+                    "ibuki_source_duration_seconds_bucket{{source=\"{source}\",operation=\"{operation}\",le=\"{bound}\"}} {}",
+                    entry.buckets[index].load(Ordering::Relaxed)
+                );
+            }
+            let calls = entry.calls.load(Ordering::Relaxed);
+            let _ = writeln!(
+                out,
+                "ibuki_source_duration_seconds_bucket{{source=\"{source}\",operation=\"{operation}\",le=\"+Inf\"}} {calls}"
+            );
+            let _ = writeln!(
+                out,
+                "ibuki_source_duration_seconds_sum{{source=\"{source}\",operation=\"{operation}\"}} {}",
+                entry.sum_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0
+            );
+            let _ = writeln!(
+                out,
+                "ibuki_source_duration_seconds_count{{source=\"{source}\",operation=\"{operation}\"}} {calls}"
+            );
+        }
+    }
+}
+
+pub struct WebsocketMetrics {
+    pub connected: u64,
+    pub disconnected: u64,
+    pub queued_messages: u64,
+}
+
+pub struct RoutePlannerMetrics {
+    pub banned_ips: u64,
+    pub available_ips: u128,
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+fn gauge(out: &mut String, name: &str, help: &str, value: impl std::fmt::Display) {
+    header(out, name, "gauge", help);
+    let _ = writeln!(out, "{name} {value}");
+}
+
+/// Renders every metric in the Prometheus text exposition format
+pub fn render(
+    stats: &ApiStats,
+    websocket: &WebsocketMetrics,
+    route_planner: Option<&RoutePlannerMetrics>,
+) -> String {
+    let mut out = String::with_capacity(4096);
+
+    gauge(
+        &mut out,
+        "ibuki_players",
+        "Number of players on this node",
+        stats.players,
+    );
+    gauge(
+        &mut out,
+        "ibuki_playing_players",
+        "Number of players currently playing a track",
+        stats.playing_players,
+    );
+    gauge(
+        &mut out,
+        "ibuki_uptime_seconds",
+        "Uptime of this node",
+        stats.uptime as f64 / 1000.0,
+    );
+
+    header(
+        &mut out,
+        "ibuki_memory_bytes",
+        "gauge",
+        "Memory statistics of this node",
+    );
+    for (kind, value) in [
+        ("free", stats.memory.free),
+        ("used", stats.memory.used),
+        ("allocated", stats.memory.allocated),
+        ("reservable", stats.memory.reservable),
+    ] {
+        let _ = writeln!(out, "ibuki_memory_bytes{{type=\"{kind}\"}} {value}");
+    }
+
+    gauge(
+        &mut out,
+        "ibuki_cpu_cores",
+        "Number of cpu cores available",
+        stats.cpu.cores,
+    );
+    gauge(
+        &mut out,
+        "ibuki_cpu_system_load",
+        "Load of the whole system",
+        stats.cpu.system_load,
+    );
+    gauge(
+        &mut out,
+        "ibuki_cpu_process_load",
+        "Load of this node",
+        stats.cpu.lavalink_load,
+    );
+
+    if let Some(frame_stats) = stats.frame_stats.as_ref() {
+        gauge(
+            &mut out,
+            "ibuki_frames_sent",
+            "Average frames sent per player in the last minute",
+            frame_stats.sent,
+        );
+        gauge(
+            &mut out,
+            "ibuki_frames_nulled",
+            "Average frames nulled per player in the last minute",
+            frame_stats.nulled,
+        );
+        gauge(
+            &mut out,
+            "ibuki_frames_deficit",
+            "Average frame deficit per player in the last minute",
+            frame_stats.deficit,
+        );
+    }
+
+    SOURCE_METRICS.render(&mut out);
+
+    if let Some(route_planner) = route_planner {
+        gauge(
+            &mut out,
+            "ibuki_routeplanner_banned_ips",
+            "Number of addresses currently banned by the route planner",
+            route_planner.banned_ips,
+        );
+        gauge(
+            &mut out,
+            "ibuki_routeplanner_available_ips",
+            "Number of addresses the route planner can still use",
+            route_planner.available_ips,
+        );
+    }
+
+    header(
+        &mut out,
+        "ibuki_websocket_sessions",
+        "gauge",
+        "Number of websocket sessions on this node",
+    );
+    let _ = writeln!(
+        out,
+        "ibuki_websocket_sessions{{state=\"connected\"}} {}",
+        websocket.connected
+    );
+    let _ = writeln!(
+        out,
+        "ibuki_websocket_sessions{{state=\"disconnected\"}} {}",
+        websocket.disconnected
+    );
+    gauge(
+        &mut out,
+        "ibuki_websocket_queued_messages",
+        "Number of outbound messages buffered for disconnected sessions",
+        websocket.queued_messages,
+    );
+
+    out
+}
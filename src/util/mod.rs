@@ -1,5 +1,6 @@
 pub mod api_stats;
 pub mod config;
+pub mod connection;
 pub mod converter;
 pub mod decoder;
 pub mod encoder;
@@ -7,9 +8,12 @@ pub mod errors;
 pub mod frame_counter;
 pub mod headers;
 pub mod http;
+pub mod logger;
 pub mod mailbox;
+pub mod metrics;
 pub mod routeplanner;
 pub mod seek;
+pub mod sliding_window;
 pub mod source;
 pub mod task;
 pub mod url;
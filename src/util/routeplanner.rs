@@ -35,7 +35,8 @@ impl FromStr for Strategy {
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct IpBlockStatus {
-    pub ip_block: String,
+    #[serde(rename = "type")]
+    pub ip_type: String,
     pub size: String,
 }
 
@@ -44,14 +45,16 @@ pub struct IpBlockStatus {
 pub struct FailingAddress {
     pub failing_address: String,
     pub failing_timestamp: u64,
+    pub failing_time: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// Both fields are null when the route planner is disabled, as Lavalink reports it
+#[derive(Debug, Clone, Default, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RoutePlannerStatus {
     #[serde(rename = "class")]
-    pub class_name: String,
-    pub details: RoutePlannerDetails,
+    pub class_name: Option<String>,
+    pub details: Option<RoutePlannerDetails>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -167,10 +170,6 @@ impl IpBlock {
         }
     }
 
-    fn to_cidr(&self) -> String {
-        format!("{}/{}", self.base_ip, self.mask)
-    }
-
     fn sub_block_count(&self) -> u128 {
         match self.base_ip {
             IpAddr::V4(_) => 1,
@@ -413,7 +412,7 @@ impl RoutePlanner {
     pub fn get_status(&self) -> RoutePlannerStatus {
         self.cleanup_expired_bans();
         let class_name = match self.strategy {
-            Strategy::RotateOnBan => "RotateOnBanIpRoutePlanner",
+            Strategy::RotateOnBan => "RotatingIpRoutePlanner",
             Strategy::LoadBalance => "BalancingIpRoutePlanner",
             Strategy::NanoSwitch => "NanoIpRoutePlanner",
             Strategy::RotatingNanoSwitch => "RotatingNanoIpRoutePlanner",
@@ -423,50 +422,80 @@ impl RoutePlanner {
         let failing_addresses: Vec<FailingAddress> = self
             .banned_ips
             .iter()
-            .map(|entry| FailingAddress {
-                failing_address: entry.key().clone(),
-                failing_timestamp: std::time::SystemTime::now()
+            .map(|entry| {
+                let banned_at = std::time::SystemTime::now()
                     .checked_sub(entry.value().banned_at.elapsed())
-                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-                    .map(|d| d.as_millis().try_into().unwrap_or(u64::MAX))
-                    .unwrap_or(0),
+                    .unwrap_or(std::time::UNIX_EPOCH);
+                FailingAddress {
+                    failing_address: entry.value().ip.to_string(),
+                    failing_timestamp: banned_at
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .map(|d| d.as_millis().try_into().unwrap_or(u64::MAX))
+                        .unwrap_or(0),
+                    failing_time: httpdate::fmt_http_date(banned_at),
+                }
             })
             .collect();
 
-        let ip_block_status = self
-            .ip_blocks
-            .first()
-            .map(|block| IpBlockStatus {
-                ip_block: block.to_cidr(),
-                size: block.size().to_string(),
-            })
-            .unwrap_or_else(|| IpBlockStatus {
-                ip_block: "0.0.0.0/32".to_string(),
-                size: "0".to_string(),
-            });
+        let ip_block_status = IpBlockStatus {
+            ip_type: match self.ip_blocks.first().map(|block| block.base_ip) {
+                Some(IpAddr::V6(_)) => "Inet6Address",
+                _ => "Inet4Address",
+            }
+            .to_string(),
+            size: self.total_slots().to_string(),
+        };
 
         let current_index = self.current_index.load(Ordering::Relaxed);
         let rotate_index = self.rotate_index.load(Ordering::Relaxed);
-        let current_ip = self
-            .ip_blocks
-            .first()
-            .and_then(|b| b.get_ip(current_index as u128))
-            .map(|ip| ip.to_string());
+
+        let mut details = RoutePlannerDetails {
+            ip_block: ip_block_status,
+            failing_addresses,
+            rotate_index: None,
+            ip_index: None,
+            current_address: None,
+            current_address_index: None,
+            block_index: None,
+        };
+
+        match self.strategy {
+            Strategy::RotateOnBan => {
+                details.rotate_index = Some(rotate_index.to_string());
+                details.ip_index = Some(current_index.to_string());
+                details.current_address = self
+                    .get_ip_from_global_index(current_index as u128 % self.total_slots().max(1))
+                    .map(|ip| ip.to_string());
+            }
+            Strategy::NanoSwitch => {
+                details.current_address_index = Some(self.current_nano_index().to_string());
+            }
+            Strategy::RotatingNanoSwitch => {
+                details.block_index = Some(rotate_index.to_string());
+                details.current_address_index = Some(self.current_nano_index().to_string());
+            }
+            Strategy::LoadBalance => {}
+        }
 
         RoutePlannerStatus {
-            class_name,
-            details: RoutePlannerDetails {
-                ip_block: ip_block_status,
-                failing_addresses,
-                rotate_index: Some(rotate_index.to_string()),
-                ip_index: Some(current_index.to_string()),
-                current_address: current_ip,
-                current_address_index: Some(current_index.to_string()),
-                block_index: Some("0".to_string()),
-            },
+            class_name: Some(class_name),
+            details: Some(details),
         }
     }
 
+    fn current_nano_index(&self) -> u128 {
+        let nano = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        nano % self.total_slots().max(1)
+    }
+
+    pub fn banned_count(&self) -> usize {
+        self.cleanup_expired_bans();
+        self.banned_ips.len()
+    }
+
     pub fn available_ips(&self) -> u128 {
         self.cleanup_expired_bans();
         let total = self.total_slots();
@@ -0,0 +1,66 @@
+use dashmap::DashMap;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Keyed sliding window counter, each key remembers the instants of its hits within the window
+pub struct SlidingWindow {
+    max_hits: u32,
+    window: Duration,
+    hits: DashMap<String, VecDeque<Instant>>,
+}
+
+impl SlidingWindow {
+    pub fn new(max_hits: u32, window: Duration) -> Self {
+        Self {
+            max_hits,
+            window,
+            hits: DashMap::new(),
+        }
+    }
+
+    /// Returns how long the key has to wait before it is allowed another hit, None if it is allowed now
+    pub fn retry_after(&self, key: &str) -> Option<Duration> {
+        if self.max_hits == 0 {
+            return None;
+        }
+
+        let now = Instant::now();
+        let mut entry = self.hits.get_mut(key)?;
+
+        while entry
+            .front()
+            .is_some_and(|hit| now.duration_since(*hit) >= self.window)
+        {
+            entry.pop_front();
+        }
+
+        if entry.len() < self.max_hits as usize {
+            return None;
+        }
+
+        entry
+            .front()
+            .map(|oldest| self.window.saturating_sub(now.duration_since(*oldest)))
+    }
+
+    pub fn hit(&self, key: &str) {
+        if self.max_hits == 0 {
+            return;
+        }
+
+        self.hits
+            .entry(key.to_string())
+            .or_default()
+            .push_back(Instant::now());
+    }
+
+    /// Drops keys that have no hits left within the window
+    pub fn cleanup(&self) {
+        let now = Instant::now();
+        self.hits.retain(|_, entry| {
+            entry
+                .back()
+                .is_some_and(|hit| now.duration_since(*hit) < self.window)
+        });
+    }
+}
@@ -1,9 +1,11 @@
 use super::errors::ResolverError;
+use super::metrics::SOURCE_METRICS;
 use crate::SOURCES;
 use crate::models::{ApiTrack, ApiTrackResult};
 use async_trait::async_trait;
 use reqwest::Client;
 use songbird::input::Input;
+use std::time::Instant;
 
 #[macro_export]
 macro_rules! register_source {
@@ -35,6 +37,21 @@ macro_rules! register_source {
     }};
 }
 
+/// Input a source opened for playback, with the integrated loudness in LUFS it reported for the track
+pub struct Playable {
+    pub input: Input,
+    pub loudness: Option<f64>,
+}
+
+impl From<Input> for Playable {
+    fn from(input: Input) -> Self {
+        Self {
+            input,
+            loudness: None,
+        }
+    }
+}
+
 pub enum Query {
     Url(String),
     Search(String),
@@ -47,7 +64,24 @@ pub trait Source: Send + Sync + 'static {
     fn parse_query(&self, url: &str) -> Option<Query>;
     async fn init(&self) -> Result<(), ResolverError>;
     async fn resolve(&self, query: Query) -> Result<Option<ApiTrackResult>, ResolverError>;
-    async fn make_playable(&self, track: ApiTrack) -> Result<Input, ResolverError>;
+    async fn make_playable(&self, track: ApiTrack) -> Result<Playable, ResolverError>;
+
+    /// Query this source resolves to tracks related to `track`, which may come from another source
+    fn related_query(&self, _track: &ApiTrack) -> Option<String> {
+        None
+    }
+
+    /// Tracks related to `track` for autoplay, resolves `related_query` unless the source has an internal lookup
+    async fn related(&self, track: &ApiTrack) -> Result<Option<ApiTrackResult>, ResolverError> {
+        let Some(query) = self
+            .related_query(track)
+            .and_then(|query| self.parse_query(&query))
+        else {
+            return Ok(None);
+        };
+
+        self.resolve(query).await
+    }
 }
 
 /// http://github.com/dtolnay/async-trait/issues/141
@@ -57,13 +91,72 @@ impl FixAsyncTraitSource {
     pub fn to_inner_ref(&self) -> &Box<dyn Source + Send + Sync> {
         &self.0
     }
+
+    /// Same as Source::resolve but records the call in the source metrics
+    pub async fn resolve(&self, query: Query) -> Result<Option<ApiTrackResult>, ResolverError> {
+        let start = Instant::now();
+        let result = self.0.resolve(query).await;
+        SOURCE_METRICS.record(
+            self.0.get_name(),
+            "resolve",
+            start.elapsed(),
+            result.is_ok(),
+        );
+        result
+    }
+
+    /// Same as Source::related but records the call in the source metrics
+    pub async fn related(&self, track: &ApiTrack) -> Result<Option<ApiTrackResult>, ResolverError> {
+        let start = Instant::now();
+        let result = self.0.related(track).await;
+        SOURCE_METRICS.record(
+            self.0.get_name(),
+            "related",
+            start.elapsed(),
+            result.is_ok(),
+        );
+        result
+    }
+
+    /// Same as Source::make_playable but records the call in the source metrics
+    pub async fn make_playable(&self, track: ApiTrack) -> Result<Playable, ResolverError> {
+        let start = Instant::now();
+        let result = self.0.make_playable(track).await;
+        SOURCE_METRICS.record(
+            self.0.get_name(),
+            "make_playable",
+            start.elapsed(),
+            result.is_ok(),
+        );
+        result
+    }
 }
 
 impl ApiTrack {
-    pub async fn make_playable(self) -> Result<Input, ResolverError> {
+    pub async fn make_playable(self) -> Result<Playable, ResolverError> {
         let Some(client) = SOURCES.get(&self.info.source_name) else {
             return Err(ResolverError::InvalidSource(self.info.source_name));
         };
-        client.to_inner_ref().make_playable(self).await
+        client.make_playable(self).await
+    }
+}
+
+/// Resolves an identifier through the first source that accepts it, if it points at a single track
+pub async fn resolve_identifier(identifier: &str) -> Option<ApiTrack> {
+    for source in SOURCES.iter() {
+        let Some(data) = source.to_inner_ref().parse_query(identifier) else {
+            continue;
+        };
+        let result = source
+            .resolve(data)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or(ApiTrackResult::Empty(None));
+
+        if let ApiTrackResult::Track(api_track) = result {
+            return Some(api_track);
+        }
     }
+    None
 }
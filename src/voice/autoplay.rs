@@ -0,0 +1,81 @@
+use crate::CONFIG;
+use crate::SOURCES;
+use crate::models::{ApiTrack, ApiTrackInfo, ApiTrackResult};
+
+/// Grace period used when the config does not set one
+const DEFAULT_GRACE_PERIOD_MS: u64 = 3000;
+
+/// Recently played tracks remembered when the config does not set a history size
+const DEFAULT_HISTORY_SIZE: usize = 50;
+
+pub fn grace_period_ms() -> u64 {
+    CONFIG
+        .autoplay
+        .as_ref()
+        .and_then(|config| config.grace_period_ms)
+        .unwrap_or(DEFAULT_GRACE_PERIOD_MS)
+}
+
+pub fn history_size() -> usize {
+    CONFIG
+        .autoplay
+        .as_ref()
+        .and_then(|config| config.history_size)
+        .unwrap_or(DEFAULT_HISTORY_SIZE)
+}
+
+/// Whether `info` is one of the `recent` tracks, by identifier or by title and author across sources
+pub fn was_played(info: &ApiTrackInfo, recent: &[ApiTrackInfo]) -> bool {
+    recent.iter().any(|played| {
+        played.identifier == info.identifier
+            || (played.title.eq_ignore_ascii_case(&info.title)
+                && played.author.eq_ignore_ascii_case(&info.author))
+    })
+}
+
+/// Tracks related to `seed` that were not played recently, from the seed's source or the fallback source
+pub async fn find_related(seed: &ApiTrack, recent: &[ApiTrackInfo]) -> Vec<ApiTrack> {
+    let fallback = CONFIG
+        .autoplay
+        .as_ref()
+        .and_then(|config| config.fallback_source.as_deref());
+
+    let sources = std::iter::once(seed.info.source_name.as_str())
+        .chain(fallback.filter(|name| *name != seed.info.source_name));
+
+    for name in sources {
+        let Some(source) = SOURCES.get(name) else {
+            continue;
+        };
+        let tracks = match source.related(seed).await {
+            Ok(Some(ApiTrackResult::Track(track))) => vec![track],
+            Ok(Some(ApiTrackResult::Playlist(playlist))) => playlist.tracks,
+            Ok(Some(ApiTrackResult::Search(tracks))) => tracks,
+            Ok(_) => Vec::new(),
+            Err(error) => {
+                tracing::warn!(
+                    "Failed to load related tracks for [{}] from {}: {}",
+                    seed.info.identifier,
+                    name,
+                    error
+                );
+                Vec::new()
+            }
+        };
+
+        let tracks: Vec<ApiTrack> = tracks
+            .into_iter()
+            .filter(|track| {
+                !track.info.is_stream
+                    && track.info.identifier != seed.info.identifier
+                    && !was_played(&track.info, recent)
+            })
+            .collect();
+
+        if !tracks.is_empty() {
+            return tracks;
+        }
+    }
+
+    Vec::new()
+}
@@ -1,10 +1,12 @@
 use super::player::{
-    Destroy, GetApiPlayerInfo, GetDriver, GetFrameCounter, GetTrackHandle, IsActive, Player,
-    PlayerUpdate, SendToPlayerWebsocket, Stop, UpdateFromInternalEvent,
+    AdvanceQueue, Destroy, EndStuckTrack, GetApiPlayerInfo, GetDriver, GetFrameCounter,
+    GetTrackHandle, IsActive, Player, PlayerUpdate, SendToPlayerWebsocket, Stop, TrackEnded,
+    UpdateFromInternalEvent,
 };
+use crate::CONFIG;
 use crate::models::{
-    ApiNodeMessage, ApiPlayerEvents, ApiPlayerUpdate, ApiTrack, ApiTrackEnd, ApiTrackStart,
-    ApiWebSocketClosed,
+    ApiNodeMessage, ApiPlayerEvents, ApiPlayerUpdate, ApiTrack, ApiTrackEnd, ApiTrackEndReason,
+    ApiTrackStart, ApiTrackStuck, ApiWebSocketClosed,
 };
 use async_trait::async_trait;
 use axum::extract::ws::{Message, Utf8Bytes};
@@ -18,14 +20,15 @@ use songbird::TrackEvent;
 use songbird::events::context_data::DisconnectReason;
 use songbird::id::{GuildId, UserId};
 use songbird::model::CloseCode;
-use songbird::tracks::{TrackHandle, TrackState};
+use songbird::tracks::{ReadyState, TrackHandle, TrackState};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
+use uuid::Uuid;
 
 enum DataResult {
     // probably usable in future
     #[allow(dead_code)]
-    Track(TrackState, Arc<ApiTrack>),
+    Track(TrackState, Arc<ApiTrack>, Uuid),
     Disconnect(i32, String),
     Empty,
 }
@@ -93,7 +96,7 @@ impl EventHandler for PlayerEvent {
 
                 let track = handle.data::<ApiTrack>();
 
-                data_result = DataResult::Track(state, track);
+                data_result = DataResult::Track(state, track, handle.uuid());
             }
             EventContext::DriverDisconnect(info) => {
                 let (code, message) = {
@@ -138,8 +141,11 @@ async fn handle_player_event(player_event: PlayerEvent, data_result: DataResult)
 
     match player_event.event {
         Event::Periodic(_, _) => {
-            if let Ok(counter) = actor_ref.ask(GetFrameCounter).await {
-                let is_playing = actor_ref.ask(IsActive).await.unwrap_or(false);
+            let is_playing = actor_ref.ask(IsActive).await.unwrap_or(false);
+
+            let counter = actor_ref.ask(GetFrameCounter).await.ok();
+
+            if let Some(counter) = counter.as_ref() {
                 counter.on_periodic(is_playing);
             }
 
@@ -150,10 +156,9 @@ async fn handle_player_event(player_event: PlayerEvent, data_result: DataResult)
                 return Some(());
             };
 
-            let updates: Vec<PlayerUpdate> = vec![
-                PlayerUpdate::Volume((state.volume * 100.0).clamp(0.0, 1000.0).round() as u32),
-                PlayerUpdate::Position(state.position.as_millis() as u32),
-            ];
+            // the player owns its volume, a filtered track or a duck leaves the songbird volume elsewhere
+            let updates: Vec<PlayerUpdate> =
+                vec![PlayerUpdate::Position(state.position.as_millis() as u32)];
 
             actor_ref
                 .ask(UpdateFromInternalEvent { updates })
@@ -161,6 +166,7 @@ async fn handle_player_event(player_event: PlayerEvent, data_result: DataResult)
                 .ok()?;
 
             let api_player = actor_ref.ask(GetApiPlayerInfo).await.ok()?;
+            let paused = api_player.paused;
 
             let data = ApiPlayerUpdate {
                 guild_id: api_player.guild_id,
@@ -174,10 +180,90 @@ async fn handle_player_event(player_event: PlayerEvent, data_result: DataResult)
                 .send_to_websocket(Message::Text(Utf8Bytes::from(serialized)))
                 .await;
 
+            let (Some(threshold), Some(counter)) = (
+                CONFIG.track_stuck_threshold_ms.filter(|ms| *ms > 0),
+                counter,
+            ) else {
+                return Some(());
+            };
+
+            // an input that never becomes playable is stuck too, counted from on_track_start
+            let loading = state.ready != ReadyState::Playable;
+            let stalled = counter.on_position(
+                state.position.as_millis() as u64,
+                (is_playing || loading) && !paused,
+            );
+
+            if stalled < threshold {
+                return Some(());
+            }
+
+            counter.reset_progress();
+
+            let track = handle.data::<ApiTrack>();
+
+            tracing::warn!(
+                "Track [{}] got stuck for {}ms in GuildId [{}], ending it",
+                track.info.identifier,
+                stalled,
+                player_event.guild_id
+            );
+
+            let event = ApiTrackStuck {
+                guild_id: player_event.guild_id.0.get(),
+                track: track.as_ref().clone(),
+                threshold_ms: threshold as usize,
+            };
+
+            let serialized = serde_json::to_string(&ApiNodeMessage::Event(Box::new(
+                ApiPlayerEvents::TrackStuckEvent(event),
+            )))
+            .ok()?;
+
+            player_event
+                .send_to_websocket(Message::Text(Utf8Bytes::from(serialized)))
+                .await;
+
+            // ended like a load failure so the queue and autoplay move on
+            if !actor_ref
+                .ask(EndStuckTrack {
+                    uuid: handle.uuid(),
+                })
+                .await
+                .ok()?
+            {
+                return Some(());
+            }
+
+            counter.on_track_end();
+
+            let event = ApiTrackEnd {
+                guild_id: player_event.guild_id.0.get(),
+                track: track.as_ref().clone(),
+                reason: ApiTrackEndReason::LoadFailed,
+            };
+
+            let serialized = serde_json::to_string(&ApiNodeMessage::Event(Box::new(
+                ApiPlayerEvents::TrackEndEvent(event),
+            )))
+            .ok()?;
+
+            player_event
+                .send_to_websocket(Message::Text(Utf8Bytes::from(serialized)))
+                .await;
+
+            actor_ref
+                .ask(AdvanceQueue {
+                    ended: track.as_ref().clone(),
+                    reason: ApiTrackEndReason::LoadFailed,
+                })
+                .await
+                .ok()?;
+
             Some(())
         }
         Event::Track(event) => {
-            let DataResult::Track(state, track) = data_result else {
+            let DataResult::Track(_, track, uuid) = data_result else {
                 tracing::warn!("Expected DataResult::Track but got a different thing");
                 return None;
             };
@@ -200,31 +286,17 @@ async fn handle_player_event(player_event: PlayerEvent, data_result: DataResult)
                     Some(())
                 }
                 TrackEvent::End => {
-                    let updates = vec![
-                        PlayerUpdate::Active(false),
-                        PlayerUpdate::Track(None),
-                        PlayerUpdate::Position(0),
-                    ];
-                    actor_ref
-                        .ask(UpdateFromInternalEvent { updates })
-                        .await
-                        .ok()?;
-                    actor_ref.ask(Stop).await.ok()?;
+                    // swapped handles end silently, the same track keeps playing on a new one
+                    let reason = actor_ref.ask(TrackEnded { uuid }).await.ok()??;
+
                     if let Ok(counter) = actor_ref.ask(GetFrameCounter).await {
                         counter.on_track_end();
                     }
 
-                    // Calculate reason based on how close to the end we were
-                    let reason = if state.position.as_millis() as u64 + 1000 >= track.info.length {
-                        "FINISHED"
-                    } else {
-                        "STOPPED"
-                    };
-
                     let event = ApiTrackEnd {
                         guild_id: player_event.guild_id.0.get(),
                         track: track.as_ref().clone(),
-                        reason: reason.to_string(),
+                        reason,
                     };
 
                     let serialized = serde_json::to_string(&ApiNodeMessage::Event(Box::new(
@@ -236,6 +308,16 @@ async fn handle_player_event(player_event: PlayerEvent, data_result: DataResult)
                         .send_to_websocket(Message::Text(Utf8Bytes::from(serialized)))
                         .await;
 
+                    if reason == ApiTrackEndReason::Finished {
+                        actor_ref
+                            .ask(AdvanceQueue {
+                                ended: track.as_ref().clone(),
+                                reason,
+                            })
+                            .await
+                            .ok()?;
+                    }
+
                     Some(())
                 }
                 TrackEvent::Error => {
@@ -259,6 +341,32 @@ async fn handle_player_event(player_event: PlayerEvent, data_result: DataResult)
                         .send_to_websocket(Message::Text(Utf8Bytes::from(serialized)))
                         .await;
 
+                    // songbird does not fire an end event for errored tracks
+                    actor_ref.ask(TrackEnded { uuid }).await.ok()??;
+
+                    let event = ApiTrackEnd {
+                        guild_id: player_event.guild_id.0.get(),
+                        track: track.as_ref().clone(),
+                        reason: ApiTrackEndReason::LoadFailed,
+                    };
+
+                    let serialized = serde_json::to_string(&ApiNodeMessage::Event(Box::new(
+                        ApiPlayerEvents::TrackEndEvent(event),
+                    )))
+                    .ok()?;
+
+                    player_event
+                        .send_to_websocket(Message::Text(Utf8Bytes::from(serialized)))
+                        .await;
+
+                    actor_ref
+                        .ask(AdvanceQueue {
+                            ended: track.as_ref().clone(),
+                            reason: ApiTrackEndReason::LoadFailed,
+                        })
+                        .await
+                        .ok()?;
+
                     Some(())
                 }
                 TrackEvent::Playable => {
@@ -1,13 +1,16 @@
-use super::player::{Connect, Player, PlayerOptions};
-use crate::models::ApiVoiceData;
+use super::player::{Connect, Destroy, IdleTime, Player, PlayerOptions, SendToPlayerWebsocket};
+use crate::CLIENTS;
+use crate::models::{ApiNodeMessage, ApiPlayerEvents, ApiVoiceData, ApiWebSocketClosed};
 use crate::util::errors::PlayerManagerError;
-use crate::ws::client::WebSocketClient;
+use crate::ws::client::{GetAllPlayers, WebSocketClient};
+use axum::extract::ws::{Message, Utf8Bytes};
 use dashmap::DashMap;
 use dashmap::mapref::one::Ref;
 use kameo::actor::{ActorRef, Spawn, WeakActorRef};
 use songbird::Config;
 use songbird::id::{GuildId, UserId};
 use std::sync::Arc;
+use std::time::Duration;
 
 pub struct CreatePlayerOptions {
     pub guild_id: GuildId,
@@ -87,3 +90,54 @@ impl Drop for PlayerManager {
         tracing::info!("PlayerManager with [UserId: {}] dropped!", self.user_id);
     }
 }
+
+/// Destroys players that went without a voice connection or a playing track for longer than `threshold`
+pub async fn reap_zombie_players(threshold: Duration) {
+    let clients: Vec<ActorRef<WebSocketClient>> =
+        CLIENTS.iter().map(|client| client.clone()).collect();
+
+    for client in clients {
+        let Ok(players) = client.ask(GetAllPlayers).await else {
+            continue;
+        };
+
+        for (guild_id, player) in players {
+            let Ok(Some(idle)) = player.ask(IdleTime).await else {
+                continue;
+            };
+
+            if idle < threshold {
+                continue;
+            }
+
+            tracing::info!(
+                "Reaping zombie player with GuildId [{}] after being idle for {:?}",
+                guild_id,
+                idle
+            );
+
+            let event = ApiWebSocketClosed {
+                guild_id: guild_id.0.get(),
+                code: 4014,
+                reason: format!(
+                    "Player was idle without a voice connection for more than {}ms",
+                    threshold.as_millis()
+                ),
+                by_remote: false,
+            };
+
+            if let Ok(serialized) = serde_json::to_string(&ApiNodeMessage::Event(Box::new(
+                ApiPlayerEvents::WebSocketClosedEvent(event),
+            ))) {
+                player
+                    .ask(SendToPlayerWebsocket {
+                        message: Message::Text(Utf8Bytes::from(serialized)),
+                    })
+                    .await
+                    .ok();
+            }
+
+            player.ask(Destroy).await.ok();
+        }
+    }
+}
@@ -1,3 +1,4 @@
+pub mod autoplay;
 pub mod events;
 pub mod manager;
 pub mod player;
@@ -1,14 +1,22 @@
+use super::autoplay;
 use super::events::PlayerEvent;
 use crate::CONFIG;
 use crate::SCHEDULER;
+use crate::filters::fade::FadeCurve;
 use crate::filters::processor::FilterChain;
-use crate::filters::source::{FilteredCompose, FilteredSource};
-use crate::models::{ApiPlayer, ApiPlayerState, ApiTrack, ApiVoiceData, Empty, LavalinkFilters};
+use crate::filters::source::{FilteredCompose, FilteredFormat, SourcePosition};
+use crate::models::{
+    ApiException, ApiLoopMode, ApiNodeMessage, ApiPlayer, ApiPlayerEvents, ApiPlayerState,
+    ApiQueue, ApiQueueUpdate, ApiTrack, ApiTrackEnd, ApiTrackEndReason, ApiTrackException,
+    ApiTrackInfo, ApiVoiceData, LavalinkFilters,
+};
+use crate::util::config::AudioFading;
 use crate::util::decoder::{decode_base64, decode_track};
-use crate::util::errors::PlayerError;
+use crate::util::errors::{PlayerError, ResolverError};
 use crate::util::frame_counter::FrameCounter;
+use crate::util::source::{Playable, resolve_identifier};
 use crate::ws::client::{SendConnectionMessage, WebSocketClient};
-use axum::extract::ws::Message;
+use axum::extract::ws::{Message, Utf8Bytes};
 use dashmap::DashMap;
 use kameo::actor::{ActorRef, WeakActorRef};
 use kameo::error::ActorStopReason;
@@ -23,10 +31,15 @@ use songbird::Event;
 use songbird::TrackEvent;
 use songbird::driver::Bitrate;
 use songbird::id::{ChannelId, GuildId, UserId};
-use songbird::input::{AudioStream, File, Input, LiveInput};
+use songbird::input::{AudioStream, AudioStreamError, Compose, Input, LiveInput, Parsed};
 use songbird::tracks::{Track, TrackHandle};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::Ordering;
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use symphonia::core::io::{MediaSource, MediaSourceStream};
+use tokio::task::block_in_place;
+use uuid::Uuid;
 
 #[derive(Debug)]
 pub enum PlayerUpdate {
@@ -39,6 +52,36 @@ pub enum PlayerUpdate {
     Active(bool),
 }
 
+/// Interval between songbird volume changes while ducking a passthrough track
+const TRACK_VOLUME_STEP: Duration = Duration::from_millis(20);
+
+/// Returns the fading config if fading is enabled
+fn fading() -> Option<&'static AudioFading> {
+    CONFIG
+        .audio
+        .as_ref()
+        .map(|audio| &audio.fading)
+        .filter(|fading| fading.enabled)
+}
+
+/// How long a preloaded stream may sit unread before the server is assumed to have closed it
+const PRELOAD_MAX_IDLE: Duration = Duration::from_secs(30);
+
+/// A track being resolved and opened ahead of the play that will use it
+struct PreloadedTrack {
+    encoded: String,
+    task: tokio::task::JoinHandle<Result<Playable, ResolverError>>,
+    created: Instant,
+}
+
+impl std::fmt::Debug for PreloadedTrack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PreloadedTrack")
+            .field("encoded", &self.encoded)
+            .finish_non_exhaustive()
+    }
+}
+
 #[derive(Debug)]
 struct PlayerInternal {
     pub actor_ref: WeakActorRef<Player>,
@@ -47,7 +90,26 @@ struct PlayerInternal {
     pub websocket: WeakActorRef<WebSocketClient>,
     pub driver: Option<Driver>,
     pub handle: Option<TrackHandle>,
+    pub end_reasons: HashMap<Uuid, ApiTrackEndReason>,
     pub end_time_task: Option<tokio::task::JoinHandle<()>>,
+    pub duck_task: Option<tokio::task::JoinHandle<()>>,
+    /// Stop that waits for the stop fade before ending the handle
+    pub stop_task: Option<tokio::task::JoinHandle<()>>,
+    /// Seek that waits for the volume dip before moving the handle
+    pub seek_task: Option<tokio::task::JoinHandle<()>>,
+    pub autoplay_task: Option<tokio::task::JoinHandle<()>>,
+    /// Recently played tracks, newest last, which autoplay will not pick again
+    pub history: VecDeque<ApiTrackInfo>,
+    pub preload: Option<PreloadedTrack>,
+    /// Resolves a nextTrack identifier before it is preloaded
+    pub preload_resolve: Option<tokio::task::JoinHandle<()>>,
+    /// Whether the current track plays its native stream without the filter chain
+    pub passthrough: bool,
+    /// Whether the current track runs through the filter chain, which then applies the player volume
+    pub filtered: bool,
+    /// Handles replaced in place by the same track or already ended by the player, their end is not reported
+    pub swapped: HashSet<Uuid>,
+    pub last_activity: Instant,
     pub players: Arc<DashMap<GuildId, ActorRef<Player>>>,
 }
 
@@ -68,7 +130,12 @@ pub struct Player {
     pub voice: ApiVoiceData,
     pub filters: LavalinkFilters,
     pub filter_chain: Arc<Mutex<FilterChain>>,
+    pub source_position: Arc<SourcePosition>,
     pub frame_counter: Arc<FrameCounter>,
+    /// Tracks played after the current one ends on its own, unused by plain Lavalink clients
+    pub queue: VecDeque<ApiTrack>,
+    pub loop_mode: ApiLoopMode,
+    pub autoplay: bool,
     internal: PlayerInternal,
 }
 
@@ -91,6 +158,27 @@ impl Actor for Player {
             task.abort();
         }
 
+        if let Some(task) = self.internal.duck_task.take() {
+            task.abort();
+        }
+
+        if let Some(task) = self.internal.autoplay_task.take() {
+            task.abort();
+        }
+
+        if let Some(preload) = self.internal.preload.take() {
+            preload.task.abort();
+        }
+
+        if let Some(task) = self.internal.preload_resolve.take() {
+            task.abort();
+        }
+
+        // the actor is gone by the time songbird fires the end event, so report it here
+        if let (Some(_), Some(track)) = (self.internal.handle.take(), self.track.take()) {
+            self.send_track_end(track, ApiTrackEndReason::Cleanup).await;
+        }
+
         if let Some(driver) = self.internal.driver.take().as_mut() {
             driver.stop();
             driver.leave();
@@ -130,13 +218,17 @@ impl Player {
         let mut player = Player {
             guild_id: options.guild_id,
             track: None,
-            volume: 80,
+            volume: 100,
             paused: false,
             state: Default::default(),
             voice: options.server_update.clone().unwrap_or_default(),
             filters: Default::default(),
             filter_chain: Arc::new(Mutex::new(FilterChain::new(48000))),
+            source_position: Arc::new(SourcePosition::default()),
             frame_counter: Arc::new(FrameCounter::new()),
+            queue: VecDeque::new(),
+            loop_mode: ApiLoopMode::Off,
+            autoplay: false,
             internal: PlayerInternal {
                 actor_ref,
                 user_id: options.user_id,
@@ -144,7 +236,19 @@ impl Player {
                 websocket: options.websocket,
                 driver: Default::default(),
                 handle: None,
+                end_reasons: HashMap::new(),
                 end_time_task: None,
+                duck_task: None,
+                stop_task: None,
+                seek_task: None,
+                autoplay_task: None,
+                history: VecDeque::new(),
+                preload: None,
+                preload_resolve: None,
+                passthrough: false,
+                filtered: false,
+                swapped: HashSet::new(),
+                last_activity: Instant::now(),
                 players: options.players,
             },
         };
@@ -166,9 +270,38 @@ impl Player {
         self.frame_counter.clone()
     }
 
+    /// Returns how long the player went without a voice connection or a playing track
+    #[message]
+    pub fn idle_time(&self) -> Option<Duration> {
+        if self.state.connected || self.internal.active {
+            return None;
+        }
+        Some(self.internal.last_activity.elapsed())
+    }
+
+    /// Returns the player with its position read from the track instead of the last player update
+    #[message]
+    pub async fn get_api_player_info(&self) -> ApiPlayer {
+        let mut player: ApiPlayer = self.into();
+
+        if let Some(handle) = self.internal.handle.as_ref() {
+            if let Ok(state) = handle.get_info().await {
+                player.state.position =
+                    self.source_position
+                        .to_source_ms(state.position.as_millis() as u64) as u32;
+            }
+        }
+
+        player
+    }
+
+    /// Source milliseconds played per wall clock millisecond
     #[message]
-    pub fn get_api_player_info(&self) -> ApiPlayer {
-        self.into()
+    pub fn playback_rate(&self) -> f64 {
+        self.filter_chain
+            .lock()
+            .map(|chain| chain.playback_rate())
+            .unwrap_or(1.0)
     }
 
     #[message]
@@ -246,6 +379,7 @@ impl Player {
 
         self.state.connected = true;
         self.voice = server_update.clone();
+        self.internal.last_activity = Instant::now();
 
         if let Some(api_track) = self.track.clone() {
             tracing::debug!(
@@ -253,56 +387,7 @@ impl Player {
                 self.guild_id
             );
 
-            let track_data = Arc::new(api_track.clone());
-            let input = api_track.make_playable().await?;
-            let input = Self::apply_filters(&self.filter_chain, self.guild_id, input);
-
-            let volume_f32 = self.volume as f32 / 100.0;
-            let track = Track::new_with_data(input, track_data).volume(volume_f32);
-
-            let track_handle = driver.play_only(track);
-
-            track_handle.add_event(
-                Event::Track(TrackEvent::Play),
-                PlayerEvent::new(
-                    Event::Track(TrackEvent::Play),
-                    self.guild_id,
-                    self.internal.user_id,
-                    self.internal.actor_ref.clone(),
-                ),
-            )?;
-
-            track_handle.add_event(
-                Event::Track(TrackEvent::Pause),
-                PlayerEvent::new(
-                    Event::Track(TrackEvent::Pause),
-                    self.guild_id,
-                    self.internal.user_id,
-                    self.internal.actor_ref.clone(),
-                ),
-            )?;
-
-            track_handle.add_event(
-                Event::Track(TrackEvent::Playable),
-                PlayerEvent::new(
-                    Event::Track(TrackEvent::Playable),
-                    self.guild_id,
-                    self.internal.user_id,
-                    self.internal.actor_ref.clone(),
-                ),
-            )?;
-
-            track_handle.add_event(
-                Event::Track(TrackEvent::End),
-                PlayerEvent::new(
-                    Event::Track(TrackEvent::End),
-                    self.guild_id,
-                    self.internal.user_id,
-                    self.internal.actor_ref.clone(),
-                ),
-            )?;
-
-            let _ = self.internal.handle.insert(track_handle);
+            self.start_track(api_track, None).await?;
         }
 
         Ok(())
@@ -314,12 +399,27 @@ impl Player {
             task.abort();
         }
 
+        if let Some(task) = self.internal.duck_task.take() {
+            task.abort();
+        }
+
+        if let Some(task) = self.internal.autoplay_task.take() {
+            task.abort();
+        }
+
+        if let Some(handle) = self.internal.handle.as_ref() {
+            self.internal
+                .end_reasons
+                .insert(handle.uuid(), ApiTrackEndReason::Cleanup);
+        }
+
         if let Some(driver) = self.internal.driver.take().as_mut() {
             driver.stop();
             driver.leave();
         }
         self.state.connected = false;
         self.internal.active = false;
+        self.internal.last_activity = Instant::now();
     }
 
     #[message(ctx)]
@@ -338,89 +438,513 @@ impl Player {
         let api_track = ApiTrack {
             encoded,
             info,
-            plugin_info: Empty,
+            plugin_info: Default::default(),
             user_data,
         };
+
+        self.play_track(api_track, false).await
+    }
+
+    async fn play_track(
+        &mut self,
+        mut api_track: ApiTrack,
+        autoplay: bool,
+    ) -> Result<(), PlayerError> {
+        // the flag reaches both the TrackStart event and the player state
+        api_track.plugin_info.autoplay = autoplay.then_some(true);
+
+        if let Some(task) = self.internal.autoplay_task.take() {
+            task.abort();
+        }
+
+        // the hint was wrong, the preloaded input would only hold its connection open
+        if self
+            .internal
+            .preload
+            .as_ref()
+            .is_some_and(|preload| preload.encoded != api_track.encoded)
+        {
+            self.set_preload(None);
+        }
+
+        self.internal.history.push_back(api_track.info.clone());
+        let overflow = self
+            .internal
+            .history
+            .len()
+            .saturating_sub(autoplay::history_size());
+        self.internal.history.drain(..overflow);
+
         self.track = Some(api_track.clone());
+        self.internal.last_activity = Instant::now();
 
         // If no driver yet (disconnected player), just queue the track
-        let Some(driver) = self.internal.driver.as_mut() else {
+        if self.internal.driver.is_none() {
             tracing::debug!(
                 "No driver yet, track queued for GuildId: [{}]",
                 self.guild_id
             );
             return Ok(());
-        };
+        }
+
+        self.start_track(api_track, None).await
+    }
 
-        // We have a driver, play the track
+    /// Loads `api_track` and replaces the current handle with it. With `resume_at` set the current
+    /// track is swapped in place at that position, without the client seeing it end and start again
+    async fn start_track(
+        &mut self,
+        api_track: ApiTrack,
+        resume_at: Option<Duration>,
+    ) -> Result<(), PlayerError> {
+        let passthrough = self.can_passthrough();
         let track_data = Arc::new(api_track.clone());
-        let input = api_track.make_playable().await?;
-        let input = Self::apply_filters(&self.filter_chain, self.guild_id, input);
 
-        let volume_f32 = self.volume as f32 / 100.0;
-        let track = Track::new_with_data(input, track_data).volume(volume_f32);
+        // a swap restarts the current track, which is never the preloaded one
+        let preload = self
+            .internal
+            .preload
+            .take_if(|preload| resume_at.is_none() && preload.encoded == api_track.encoded);
+        let playable = match preload {
+            Some(preload) => match preload.task.await {
+                Ok(Ok(playable)) if preload.created.elapsed() > PRELOAD_MAX_IDLE => {
+                    tracing::debug!(
+                        "The preloaded input of [{}] sat idle for GuildId [{}], reopening it",
+                        track_data.info.identifier,
+                        self.guild_id
+                    );
+                    Self::reopen(playable, api_track.clone()).await
+                }
+                Ok(Ok(playable)) => {
+                    tracing::debug!(
+                        "Using the preloaded input of [{}] for GuildId [{}]",
+                        track_data.info.identifier,
+                        self.guild_id
+                    );
+                    Ok(playable)
+                }
+                _ => api_track.make_playable().await,
+            },
+            None => api_track.make_playable().await,
+        };
 
-        // todo: before sending the new track, we may want to send a replaced notification from here before playing the new track
+        let source_loudness = playable
+            .as_ref()
+            .ok()
+            .and_then(|playable| playable.loudness);
 
-        let track_handle = driver.play_only(track);
+        let loaded = match playable.map(|playable| playable.input) {
+            Ok(input) if passthrough => {
+                tracing::debug!(
+                    "No active filters for GuildId [{}], keeping the native stream",
+                    self.guild_id
+                );
+                self.source_position.clear();
+                Ok((input, None))
+            }
+            Ok(input) => {
+                Self::apply_filters(
+                    &self.filter_chain,
+                    &self.source_position,
+                    self.guild_id,
+                    input,
+                    (!track_data.info.is_stream).then_some(track_data.info.length),
+                )
+                .await
+            }
+            Err(error) => Err(error),
+        };
+        let (input, filter_error) = match loaded {
+            Ok(loaded) => loaded,
+            Err(error) if resume_at.is_some() => {
+                tracing::warn!(
+                    "Failed to reload the track for filtering in GuildId [{}], it keeps playing unfiltered: {}",
+                    self.guild_id,
+                    error
+                );
+                return Ok(());
+            }
+            Err(error) => {
+                // a play always replaces whatever was playing, even if the new track fails to load
+                self.cancel_pending_tasks();
+                if let Some(handle) = self.internal.handle.as_ref() {
+                    // a stop still fading out keeps its reason
+                    self.internal
+                        .end_reasons
+                        .entry(handle.uuid())
+                        .or_insert(ApiTrackEndReason::Replaced);
+                    handle.stop().ok();
+                }
+                self.track = None;
+                self.send_load_failed(track_data.as_ref().clone(), error)
+                    .await;
+                return Ok(());
+            }
+        };
 
-        self.frame_counter.on_track_start();
+        // the old handle's delayed stop or seek would otherwise touch the new track's fades
+        if resume_at.is_none() {
+            self.cancel_pending_tasks();
+        }
 
-        track_handle.add_event(
-            Event::Track(TrackEvent::Play),
-            PlayerEvent::new(
-                Event::Track(TrackEvent::Play),
-                self.guild_id,
-                self.internal.user_id,
-                self.internal.actor_ref.clone(),
+        // the old handle kept playing while the track reloaded, so resume from where it is now
+        let resume_at = match (resume_at, self.internal.handle.clone()) {
+            (Some(position), Some(handle)) => Some(
+                handle
+                    .get_info()
+                    .await
+                    .map(|state| state.position)
+                    .unwrap_or(position),
             ),
-        )?;
+            (resume_at, _) => resume_at,
+        };
 
-        track_handle.add_event(
-            Event::Track(TrackEvent::Pause),
-            PlayerEvent::new(
-                Event::Track(TrackEvent::Pause),
-                self.guild_id,
-                self.internal.user_id,
-                self.internal.actor_ref.clone(),
-            ),
-        )?;
+        Self::prepare_fades(&self.filter_chain, &track_data);
 
-        track_handle.add_event(
-            Event::Track(TrackEvent::Error),
-            PlayerEvent::new(
-                Event::Track(TrackEvent::Error),
-                self.guild_id,
-                self.internal.user_id,
-                self.internal.actor_ref.clone(),
-            ),
-        )?;
+        // a swapped track keeps its loudness measurement
+        if resume_at.is_none() {
+            if let Ok(mut chain) = self.filter_chain.lock() {
+                chain.start_track(source_loudness);
+            }
+        }
 
-        track_handle.add_event(
-            Event::Track(TrackEvent::Playable),
-            PlayerEvent::new(
-                Event::Track(TrackEvent::Playable),
-                self.guild_id,
-                self.internal.user_id,
-                self.internal.actor_ref.clone(),
-            ),
-        )?;
+        // the chain applies the volume ahead of its limiter, songbird only scales what skips the chain
+        let filtered = !passthrough && filter_error.is_none();
+        let volume_f32 = if filtered {
+            1.0
+        } else {
+            self.volume as f32 / 100.0
+        };
+        let mut track = Track::new_with_data(input, track_data.clone()).volume(volume_f32);
+
+        if resume_at.is_some() && self.paused {
+            track = track.pause();
+        }
 
-        track_handle.add_event(
-            Event::Track(TrackEvent::End),
-            PlayerEvent::new(
-                Event::Track(TrackEvent::End),
+        if let Some(handle) = self.internal.handle.as_ref() {
+            if resume_at.is_some() {
+                self.internal.swapped.insert(handle.uuid());
+            } else {
+                self.internal
+                    .end_reasons
+                    .entry(handle.uuid())
+                    .or_insert(ApiTrackEndReason::Replaced);
+            }
+        }
+
+        let Some(driver) = self.internal.driver.as_mut() else {
+            return Ok(());
+        };
+
+        let track_handle = driver.play_only(track);
+
+        if let Some(position) = resume_at {
+            let _ = track_handle.seek(position);
+        } else {
+            self.frame_counter.on_track_start();
+        }
+
+        for event in [
+            TrackEvent::Play,
+            TrackEvent::Pause,
+            TrackEvent::Error,
+            TrackEvent::Playable,
+            TrackEvent::End,
+        ] {
+            let handler = PlayerEvent::new(
+                Event::Track(event),
                 self.guild_id,
                 self.internal.user_id,
                 self.internal.actor_ref.clone(),
-            ),
-        )?;
+            );
+
+            // the client already got a TrackStart for a swapped track
+            if resume_at.is_some() && event == TrackEvent::Playable {
+                handler.fired.store(true, Ordering::Release);
+            }
+
+            track_handle.add_event(Event::Track(event), handler)?;
+        }
 
         let _ = self.internal.handle.insert(track_handle);
+        self.internal.passthrough = passthrough;
+        self.internal.filtered = filtered;
+
+        if let Some(reason) = filter_error {
+            self.send_filter_fallback(track_data.as_ref().clone(), reason)
+                .await;
+        }
 
         Ok(())
     }
 
+    #[message]
+    pub fn get_queue(&self) -> ApiQueue {
+        ApiQueue {
+            tracks: self.queue.iter().cloned().collect(),
+            loop_mode: self.loop_mode,
+        }
+    }
+
+    /// Queues `tracks` at `index`, or at the end without one, and starts them if nothing is playing.
+    /// None means the index is past the end of the queue
+    #[message]
+    pub async fn enqueue(
+        &mut self,
+        tracks: Vec<ApiTrack>,
+        index: Option<usize>,
+    ) -> Result<Option<ApiQueue>, PlayerError> {
+        let index = index.unwrap_or(self.queue.len());
+        if index > self.queue.len() {
+            return Ok(None);
+        }
+
+        for (offset, track) in tracks.into_iter().enumerate() {
+            self.queue.insert(index + offset, track);
+        }
+
+        if self.track.is_none() {
+            self.play_next().await?;
+        }
+
+        Ok(Some(self.get_queue()))
+    }
+
+    #[message]
+    pub fn remove_queued(&mut self, index: usize) -> Option<ApiQueue> {
+        self.queue.remove(index)?;
+        Some(self.get_queue())
+    }
+
+    #[message]
+    pub fn move_queued(&mut self, from: usize, to: usize) -> Option<ApiQueue> {
+        if to >= self.queue.len() {
+            return None;
+        }
+        let track = self.queue.remove(from)?;
+        self.queue.insert(to, track);
+        Some(self.get_queue())
+    }
+
+    #[message]
+    pub fn shuffle_queue(&mut self) -> ApiQueue {
+        fastrand::shuffle(self.queue.make_contiguous());
+        self.get_queue()
+    }
+
+    #[message]
+    pub fn clear_queue(&mut self) -> ApiQueue {
+        self.queue.clear();
+        self.get_queue()
+    }
+
+    #[message]
+    pub fn set_loop_mode(&mut self, loop_mode: ApiLoopMode) -> ApiQueue {
+        self.loop_mode = loop_mode;
+        self.get_queue()
+    }
+
+    /// Moves on to the next queued track once `ended` is done, following the loop mode
+    #[message]
+    pub async fn advance_queue(
+        &mut self,
+        ended: ApiTrack,
+        reason: ApiTrackEndReason,
+    ) -> Result<(), PlayerError> {
+        // the client already started something else
+        if self.track.is_some() {
+            return Ok(());
+        }
+
+        let finished = reason == ApiTrackEndReason::Finished;
+        match self.loop_mode {
+            ApiLoopMode::Track if finished => self.queue.push_front(ended.clone()),
+            ApiLoopMode::Queue if finished => self.queue.push_back(ended.clone()),
+            _ => {}
+        }
+
+        if self.queue.is_empty() {
+            self.expire_preload();
+
+            // a stuck or broken track should not end autoplay either
+            let ran_dry = matches!(
+                reason,
+                ApiTrackEndReason::Finished | ApiTrackEndReason::LoadFailed
+            );
+            if ran_dry && self.autoplay {
+                self.schedule_autoplay(ended);
+            }
+            return Ok(());
+        }
+
+        self.play_next().await
+    }
+
+    /// Plays queued tracks until one loads, then tells the client what the queue looks like now
+    async fn play_next(&mut self) -> Result<(), PlayerError> {
+        while let Some(track) = self.queue.pop_front() {
+            self.play_track(track, false).await?;
+
+            if self.track.is_some() {
+                break;
+            }
+        }
+
+        if let Some(next) = self.queue.front().cloned() {
+            self.set_preload(Some(next));
+        }
+
+        let event = ApiQueueUpdate {
+            guild_id: self.guild_id.0.get(),
+            track: self.track.clone(),
+            tracks: self.queue.iter().cloned().collect(),
+            loop_mode: self.loop_mode,
+        };
+
+        self.send_event(ApiPlayerEvents::QueueUpdateEvent(event))
+            .await;
+
+        Ok(())
+    }
+
+    #[message]
+    pub fn set_autoplay(&mut self, autoplay: bool) {
+        self.autoplay = autoplay;
+
+        if !autoplay {
+            if let Some(task) = self.internal.autoplay_task.take() {
+                task.abort();
+            }
+        }
+    }
+
+    /// Recently played tracks, or None once the client played something or turned autoplay off
+    #[message]
+    pub fn autoplay_history(&self) -> Option<Vec<ApiTrackInfo>> {
+        if self.track.is_some() || !self.autoplay {
+            return None;
+        }
+        Some(self.internal.history.iter().cloned().collect())
+    }
+
+    /// Plays the first of `candidates` that loads, unless the client started something meanwhile
+    #[message]
+    pub async fn play_autoplay(&mut self, candidates: Vec<ApiTrack>) -> Result<(), PlayerError> {
+        // dropping the handle instead of aborting it, this message is awaited by that task
+        self.internal.autoplay_task = None;
+
+        if self.track.is_some() || !self.autoplay {
+            return Ok(());
+        }
+
+        for track in candidates {
+            let recent = self.internal.history.make_contiguous();
+            if autoplay::was_played(&track.info, recent) {
+                continue;
+            }
+
+            tracing::debug!(
+                "Autoplaying [{}] in GuildId [{}]",
+                track.info.identifier,
+                self.guild_id
+            );
+
+            self.play_track(track, true).await?;
+
+            if self.track.is_some() {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Picks a track related to `seed` unless the client plays something within the grace period
+    fn schedule_autoplay(&mut self, seed: ApiTrack) {
+        let player_ref = self.internal.actor_ref.clone();
+        let guild_id = self.guild_id;
+
+        let task = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(autoplay::grace_period_ms())).await;
+
+            let Some(player) = player_ref.upgrade() else {
+                return;
+            };
+            let Ok(Some(recent)) = player.ask(AutoplayHistory).await else {
+                return;
+            };
+
+            let candidates = autoplay::find_related(&seed, &recent).await;
+            if candidates.is_empty() {
+                tracing::debug!(
+                    "No related tracks found for [{}] in GuildId [{}], autoplay stops here",
+                    seed.info.identifier,
+                    guild_id
+                );
+                return;
+            }
+
+            if let Err(error) = player.ask(PlayAutoplay { candidates }).await {
+                tracing::warn!("Failed to autoplay in GuildId [{}]: {}", guild_id, error);
+            }
+        });
+
+        if let Some(old_task) = self.internal.autoplay_task.replace(task) {
+            old_task.abort();
+        }
+    }
+
+    /// Whether a new track can skip the filter chain so songbird may pass Opus straight through.
+    /// Songbird only does that for a track volume of exactly 1.0, the player volume of 100
+    fn can_passthrough(&self) -> bool {
+        let reason = if (self.volume as f32 / 100.0 - 1.0).abs() > f32::EPSILON {
+            Some("the volume is not 100")
+        } else if fading().is_some() {
+            Some("fades are configured")
+        } else if !self.filter_chain.lock().is_ok_and(|chain| chain.is_idle()) {
+            Some("filters, ducking or silence skipping are active")
+        } else {
+            None
+        };
+
+        if let Some(reason) = reason {
+            tracing::debug!(
+                "Passthrough skipped for GuildId [{}]: {}",
+                self.guild_id,
+                reason
+            );
+        }
+
+        reason.is_none()
+    }
+
+    /// Restarts a track that started on its native stream through the filter chain, once it needs filtering
+    async fn leave_passthrough(&mut self) -> Result<(), PlayerError> {
+        if !self.internal.passthrough || self.filter_chain.lock().is_ok_and(|chain| chain.is_idle())
+        {
+            return Ok(());
+        }
+
+        let (Some(handle), Some(track)) = (self.internal.handle.clone(), self.track.clone()) else {
+            self.internal.passthrough = false;
+            return Ok(());
+        };
+
+        let position = handle
+            .get_info()
+            .await
+            .map(|state| state.position)
+            .unwrap_or_default();
+
+        tracing::debug!(
+            "Filters enabled for GuildId [{}], moving the track to the filtered path at {:?}",
+            self.guild_id,
+            position
+        );
+
+        self.start_track(track, Some(position)).await
+    }
+
     #[message]
     pub async fn stop(&mut self) {
         if let Some(task) = self.internal.end_time_task.take() {
@@ -430,7 +954,50 @@ impl Player {
         let Some(handle) = self.internal.handle.as_ref() else {
             return;
         };
-        handle.stop().ok();
+
+        self.internal
+            .end_reasons
+            .insert(handle.uuid(), ApiTrackEndReason::Stopped);
+
+        let fade = fading()
+            .filter(|_| self.internal.active && !self.paused)
+            .map(|fading| {
+                (
+                    Duration::from_millis(fading.track_stop.duration),
+                    FadeCurve::from(fading.track_stop.curve.as_str()),
+                )
+            })
+            .filter(|(duration, _)| !duration.is_zero());
+
+        let Some((duration, curve)) = fade else {
+            handle.stop().ok();
+            return;
+        };
+
+        if let Ok(mut chain) = self.filter_chain.lock() {
+            chain.fader_mut().fade_out(duration, curve);
+        }
+
+        let handle = handle.clone();
+        let task = tokio::spawn(async move {
+            tokio::time::sleep(duration).await;
+            handle.stop().ok();
+        });
+
+        if let Some(old_task) = self.internal.stop_task.replace(task) {
+            old_task.abort();
+        }
+    }
+
+    /// Drops the delayed stop and seek of the current handle once another track takes over
+    fn cancel_pending_tasks(&mut self) {
+        if let Some(task) = self.internal.stop_task.take() {
+            task.abort();
+        }
+
+        if let Some(task) = self.internal.seek_task.take() {
+            task.abort();
+        }
     }
 
     #[message]
@@ -448,12 +1015,52 @@ impl Player {
             return;
         };
 
-        let result = handle
-            .seek_async(Duration::from_millis(position as u64))
-            .await;
+        // dips the volume around the seek, half of the duration on each side
+        let dip = fading()
+            .filter(|_| self.internal.active && !self.paused)
+            .map(|fading| {
+                (
+                    Duration::from_millis(fading.seek.duration / 2),
+                    FadeCurve::from(fading.seek.curve.as_str()),
+                )
+            })
+            .filter(|(duration, _)| !duration.is_zero());
+
+        let Some((duration, curve)) = dip else {
+            if handle
+                .seek_async(Duration::from_millis(position as u64))
+                .await
+                .is_ok()
+            {
+                self.state.position = position;
+            }
+            return;
+        };
+
+        if let Ok(mut chain) = self.filter_chain.lock() {
+            chain.fader_mut().fade_out(duration, curve);
+        }
+
+        // the dip runs outside the actor so its mailbox is not held up for the fade,
+        // the periodic update corrects the position if the seek fails
+        self.state.position = position;
+
+        let handle = handle.clone();
+        let filter_chain = self.filter_chain.clone();
+        let task = tokio::spawn(async move {
+            tokio::time::sleep(duration).await;
 
-        if result.is_ok() {
-            self.state.position = position;
+            let _ = handle
+                .seek_async(Duration::from_millis(position as u64))
+                .await;
+
+            if let Ok(mut chain) = filter_chain.lock() {
+                chain.fader_mut().resume(duration, curve);
+            }
+        });
+
+        if let Some(old_task) = self.internal.seek_task.replace(task) {
+            old_task.abort();
         }
     }
 
@@ -481,9 +1088,115 @@ impl Player {
         self.paused = pause;
     }
 
+    /// Ramps the track down to the ducking target volume, released after `duration` ms if provided
+    #[message]
+    pub async fn duck(&mut self, duration: Option<u64>) {
+        let Some(ducking) = CONFIG.audio.as_ref().map(|audio| &audio.fading.ducking) else {
+            return;
+        };
+
+        if let Some(task) = self.internal.duck_task.take() {
+            task.abort();
+        }
+
+        let ramp = Duration::from_millis(ducking.duration);
+        let curve = FadeCurve::from(ducking.curve.as_str());
+
+        let target = ducking.target_volume as f32;
+
+        // the chain carries the duck over to the next track even while this one skips it
+        if let Ok(mut chain) = self.filter_chain.lock() {
+            chain.duck(target, ramp, curve);
+        }
+
+        // a passthrough track is ducked through its songbird volume instead of reloading it into the chain
+        let handle = self
+            .internal
+            .passthrough
+            .then(|| self.internal.handle.clone())
+            .flatten();
+
+        if handle.is_none() && duration.is_none() {
+            return;
+        }
+
+        let filter_chain = self.filter_chain.clone();
+        let volume = self.volume as f32 / 100.0;
+
+        self.internal.duck_task = Some(tokio::spawn(async move {
+            match &handle {
+                Some(handle) => {
+                    Self::ramp_track_volume(handle, volume, 1.0, target, ramp, curve).await
+                }
+                None => tokio::time::sleep(ramp).await,
+            }
+
+            let Some(duration) = duration else {
+                return;
+            };
+
+            tokio::time::sleep(Duration::from_millis(duration)).await;
+            if let Ok(mut chain) = filter_chain.lock() {
+                chain.duck(1.0, ramp, curve);
+            }
+            if let Some(handle) = &handle {
+                Self::ramp_track_volume(handle, volume, target, 1.0, ramp, curve).await;
+            }
+        }));
+    }
+
+    /// Ramps the track back up to its normal volume
+    #[message]
+    pub fn release_duck(&mut self) {
+        let Some(ducking) = CONFIG.audio.as_ref().map(|audio| &audio.fading.ducking) else {
+            return;
+        };
+
+        if let Some(task) = self.internal.duck_task.take() {
+            task.abort();
+        }
+
+        let ramp = Duration::from_millis(ducking.duration);
+        let curve = FadeCurve::from(ducking.curve.as_str());
+
+        if let Ok(mut chain) = self.filter_chain.lock() {
+            chain.duck(1.0, ramp, curve);
+        }
+
+        if let (true, Some(handle)) = (self.internal.passthrough, self.internal.handle.clone()) {
+            let volume = self.volume as f32 / 100.0;
+            let target = ducking.target_volume as f32;
+            self.internal.duck_task = Some(tokio::spawn(async move {
+                Self::ramp_track_volume(&handle, volume, target, 1.0, ramp, curve).await;
+            }));
+        }
+    }
+
+    /// Steps the songbird volume of a passthrough track from `from` to `to` times `volume` over `ramp`
+    async fn ramp_track_volume(
+        handle: &TrackHandle,
+        volume: f32,
+        from: f32,
+        to: f32,
+        ramp: Duration,
+        curve: FadeCurve,
+    ) {
+        let steps = (ramp.as_millis() / TRACK_VOLUME_STEP.as_millis()).max(1) as u32;
+
+        for step in 1..=steps {
+            let gain = from + (to - from) * curve.shape(step as f32 / steps as f32);
+            if handle.set_volume(volume * gain).is_err() {
+                return;
+            }
+            if step < steps {
+                tokio::time::sleep(TRACK_VOLUME_STEP).await;
+            }
+        }
+    }
+
     #[message]
     pub async fn set_volume(&mut self, volume: f32) {
-        let Some(handle) = self.internal.handle.as_ref() else {
+        let Some(handle) = self.internal.handle.clone() else {
             tracing::debug!(
                 "Cannot set volume for GuildId [{}]: no active track handle",
                 self.guild_id
@@ -492,19 +1205,41 @@ impl Player {
         };
 
         let volume_f32 = volume / 100.0;
-        match handle.set_volume(volume_f32) {
-            Ok(_) => {
-                self.volume = volume as u32;
-                tracing::debug!(
-                    "Volume set to {} (raw {}) for GuildId: [{}]",
-                    volume_f32,
-                    volume,
-                    self.guild_id
+
+        // a track that skips the chain has no limiter, so a boost moves a passthrough track into the chain
+        if !self.internal.filtered {
+            let track_volume = if self.internal.passthrough {
+                volume_f32.min(1.0)
+            } else {
+                volume_f32
+            };
+
+            if let Err(e) = handle.set_volume(track_volume) {
+                tracing::warn!(
+                    "Failed to set volume for GuildId [{}]: {:?}",
+                    self.guild_id,
+                    e
                 );
+                return;
             }
-            Err(e) => {
+        }
+
+        if let Ok(mut chain) = self.filter_chain.lock() {
+            chain.set_player_volume(volume_f32);
+        }
+
+        self.volume = volume as u32;
+        tracing::debug!(
+            "Volume set to {} (raw {}) for GuildId: [{}]",
+            volume_f32,
+            volume,
+            self.guild_id
+        );
+
+        if volume_f32 > 1.0 {
+            if let Err(e) = self.leave_passthrough().await {
                 tracing::warn!(
-                    "Failed to set volume for GuildId [{}]: {:?}",
+                    "Failed to move the track into the filter chain for GuildId [{}]: {:?}",
                     self.guild_id,
                     e
                 );
@@ -512,57 +1247,293 @@ impl Player {
         }
     }
 
-    fn apply_filters(
+    /// Fades in the start of the track and sets up the fade out before its natural end
+    fn prepare_fades(filter_chain: &Arc<Mutex<FilterChain>>, track: &ApiTrack) {
+        let Some(fading) = fading() else {
+            return;
+        };
+
+        let Ok(mut chain) = filter_chain.lock() else {
+            return;
+        };
+
+        let fader = chain.fader_mut();
+
+        fader.fade_in(
+            Duration::from_millis(fading.track_start.duration),
+            FadeCurve::from(fading.track_start.curve.as_str()),
+        );
+
+        fader.set_track_end(
+            (!track.info.is_stream).then_some(track.info.length),
+            Duration::from_millis(fading.track_end.duration),
+            FadeCurve::from(fading.track_end.curve.as_str()),
+        );
+    }
+
+    /// Routes the input through the filter chain. Returns the reason alongside the input when it has to play
+    /// unfiltered, and fails only when probing consumed a stream that cannot be recreated.
+    /// `length_ms` is the track length, None for streams
+    async fn apply_filters(
         filter_chain: &Arc<Mutex<FilterChain>>,
+        position: &Arc<SourcePosition>,
         guild_id: GuildId,
         input: Input,
-    ) -> Input {
-        match input {
+        length_ms: Option<u64>,
+    ) -> Result<(Input, Option<String>), ResolverError> {
+        // the previous track's mapping must not leak into an unfiltered input
+        position.clear();
+
+        let (live, compose) = match input {
             Input::Lazy(compose) => {
-                tracing::debug!(
-                    "Wrapping lazy input with FilteredCompose for GuildId [{guild_id}]"
-                );
-                Input::Lazy(Box::new(FilteredCompose::new(
-                    compose,
-                    filter_chain.clone(),
-                    48000,
-                    2,
-                )))
-            }
-            Input::Live(LiveInput::Raw(stream), data) => {
-                tracing::debug!(
-                    "Live raw input detected for GuildId [{guild_id}]. Attempting filter wrap..."
+                let (compose, stream) = Self::create_stream(compose).await;
+                match stream {
+                    Ok(stream) => (LiveInput::Raw(stream), Some(compose)),
+                    Err(e) => {
+                        return Ok((
+                            Input::Lazy(compose),
+                            Some(format!("Input could not be created: {e}")),
+                        ));
+                    }
+                }
+            }
+            Input::Live(live, compose) => (live, compose),
+        };
+
+        let parsed = match Self::probe_live(live) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                tracing::warn!("Probing input failed for GuildId [{guild_id}]: {e}");
+                return match compose {
+                    Some(compose) => Ok((Input::Lazy(compose), Some(e.to_string()))),
+                    None => Err(ResolverError::Custom(e.to_string())),
+                };
+            }
+        };
+
+        match FilteredFormat::wrap(parsed, filter_chain.clone(), position.clone(), length_ms) {
+            Ok(filtered) => {
+                // songbird recreates the input through the compose to seek back on streams that cannot
+                let compose = compose.map(|compose| {
+                    Box::new(FilteredCompose::new(
+                        compose,
+                        filter_chain.clone(),
+                        position.clone(),
+                        length_ms,
+                    )) as Box<dyn Compose>
+                });
+                Ok((Input::Live(LiveInput::Parsed(filtered), compose), None))
+            }
+            Err((parsed, e)) => {
+                tracing::warn!(
+                    "FilteredFormat creation failed for GuildId [{guild_id}]: {e}. Playing unfiltered"
                 );
+                position.clear();
+                Ok((
+                    Input::Live(LiveInput::Parsed(parsed), compose),
+                    Some(e.to_string()),
+                ))
+            }
+        }
+    }
 
-                let hint = stream.hint.unwrap_or_default();
-                match FilteredSource::new(stream.input, hint, filter_chain.clone(), 48000, 2) {
-                    Ok(filtered) => {
-                        let out = AudioStream {
-                            input: Box::new(filtered) as Box<dyn symphonia::core::io::MediaSource>,
-                            hint: Some({
-                                let mut h = symphonia::core::probe::Hint::new();
-                                h.with_extension("wav");
-                                h
-                            }),
-                        };
-                        Input::Live(LiveInput::Raw(out), data)
-                    }
-                    Err(e) => {
-                        tracing::error!(
-                            "FilteredSource creation failed for GuildId [{guild_id}]: {e}. \
-                             Track will not play."
-                        );
-                        Input::Lazy(Box::new(File::new("__filter_error_unsupported_codec__")))
+    /// Probes a live input into a parsed one, which is what the filters and songbird's mixer consume
+    fn probe_live(live: LiveInput) -> Result<Parsed, std::io::Error> {
+        match live {
+            LiveInput::Parsed(parsed) => Ok(parsed),
+            LiveInput::Raw(stream) => block_in_place(|| {
+                FilteredFormat::probe(
+                    MediaSourceStream::new(stream.input, Default::default()),
+                    &stream.hint.unwrap_or_default(),
+                )
+            }),
+            LiveInput::Wrapped(stream) => block_in_place(|| {
+                FilteredFormat::probe(stream.input, &stream.hint.unwrap_or_default())
+            }),
+        }
+    }
+
+    /// Resolves `api_track` and opens its stream up to the first packets, the input the play will need
+    async fn prefetch(api_track: ApiTrack) -> Result<Playable, ResolverError> {
+        let Playable { input, loudness } = api_track.make_playable().await?;
+
+        let (live, compose) = match input {
+            Input::Lazy(compose) => {
+                let (compose, stream) = Self::create_stream(compose).await;
+                match stream {
+                    Ok(stream) => (LiveInput::Raw(stream), Some(compose)),
+                    // the play creates it again, the resolved compose is still worth keeping
+                    Err(_) => {
+                        return Ok(Playable {
+                            input: Input::Lazy(compose),
+                            loudness,
+                        });
                     }
                 }
             }
-            other => {
-                tracing::debug!(
-                    "Input type cannot be filtered for GuildId [{guild_id}], playing unfiltered"
+            Input::Live(live, compose) => (live, compose),
+        };
+
+        let input = match (Self::probe_live(live), compose) {
+            (Ok(parsed), compose) => Input::Live(LiveInput::Parsed(parsed), compose),
+            (Err(_), Some(compose)) => Input::Lazy(compose),
+            (Err(e), None) => return Err(ResolverError::Custom(e.to_string())),
+        };
+
+        Ok(Playable { input, loudness })
+    }
+
+    /// Resolves and opens `encoded` in the background so a later play of it starts without delay.
+    /// None drops the current preload
+    #[message]
+    pub fn preload(&mut self, encoded: Option<String>) -> Result<(), PlayerError> {
+        let track = match encoded {
+            Some(encoded) => {
+                let info = decode_track(&encoded).or_else(|_| decode_base64(&encoded))?;
+                Some(ApiTrack {
+                    encoded,
+                    info,
+                    plugin_info: Default::default(),
+                    user_data: None,
+                })
+            }
+            None => None,
+        };
+
+        self.set_preload(track);
+
+        Ok(())
+    }
+
+    fn set_preload(&mut self, track: Option<ApiTrack>) {
+        // clients may repeat the same hint on every update
+        if let (Some(preload), Some(track)) = (self.internal.preload.as_ref(), track.as_ref()) {
+            if preload.encoded == track.encoded {
+                return;
+            }
+        }
+
+        if let Some(preload) = self.internal.preload.take() {
+            preload.task.abort();
+        }
+
+        if let Some(task) = self.internal.preload_resolve.take() {
+            task.abort();
+        }
+
+        let Some(track) = track else {
+            return;
+        };
+
+        tracing::debug!(
+            "Preloading [{}] for GuildId [{}]",
+            track.info.identifier,
+            self.guild_id
+        );
+
+        self.internal.preload = Some(PreloadedTrack {
+            encoded: track.encoded.clone(),
+            task: tokio::spawn(Self::prefetch(track)),
+            created: Instant::now(),
+        });
+    }
+
+    /// Resolves `identifier` in the background and preloads the track it points at, replacing the current hint
+    #[message]
+    pub fn preload_identifier(&mut self, identifier: String) {
+        self.set_preload(None);
+
+        let player_ref = self.internal.actor_ref.clone();
+        let guild_id = self.guild_id;
+
+        self.internal.preload_resolve = Some(tokio::spawn(async move {
+            let Some(api_track) = resolve_identifier(&identifier).await else {
+                tracing::warn!(
+                    "nextTrack identifier [{}] did not resolve to a track for GuildId [{}], nothing is preloaded",
+                    identifier,
+                    guild_id
                 );
-                other
+                return;
+            };
+
+            let Some(player) = player_ref.upgrade() else {
+                return;
+            };
+            let _ = player
+                .ask(Preload {
+                    encoded: Some(api_track.encoded),
+                })
+                .await;
+        }));
+    }
+
+    /// Drops the preload if nothing played it within the autoplay grace period after the current track ended
+    fn expire_preload(&self) {
+        let Some(encoded) = self
+            .internal
+            .preload
+            .as_ref()
+            .map(|preload| preload.encoded.clone())
+        else {
+            return;
+        };
+
+        let player_ref = self.internal.actor_ref.clone();
+
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(autoplay::grace_period_ms())).await;
+
+            if let Some(player) = player_ref.upgrade() {
+                let _ = player.ask(DropIdlePreload { encoded }).await;
             }
+        });
+    }
+
+    #[message]
+    pub fn drop_idle_preload(&mut self, encoded: String) {
+        // a track started since then, it either used the preload or the hint is for after it
+        if self.track.is_some() {
+            return;
         }
+
+        if let Some(preload) = self
+            .internal
+            .preload
+            .take_if(|preload| preload.encoded == encoded)
+        {
+            tracing::debug!(
+                "Dropping the unused preload for GuildId [{}]",
+                self.guild_id
+            );
+            preload.task.abort();
+        }
+    }
+
+    /// Opens a preloaded input again through its compose, resolving the track anew when it has none
+    async fn reopen(playable: Playable, api_track: ApiTrack) -> Result<Playable, ResolverError> {
+        match playable.input {
+            Input::Live(_, Some(compose)) | Input::Lazy(compose) => Ok(Playable {
+                input: Input::Lazy(compose),
+                loudness: playable.loudness,
+            }),
+            Input::Live(_, None) => api_track.make_playable().await,
+        }
+    }
+
+    /// Creates the stream of a lazy input, handing the compose back so it can still be played unfiltered
+    async fn create_stream(
+        mut compose: Box<dyn Compose>,
+    ) -> (
+        Box<dyn Compose>,
+        Result<AudioStream<Box<dyn MediaSource>>, AudioStreamError>,
+    ) {
+        let stream = if compose.should_create_async() {
+            compose.create_async().await
+        } else {
+            block_in_place(|| compose.create())
+        };
+
+        (compose, stream)
     }
 
     #[message]
@@ -587,7 +1558,7 @@ impl Player {
                 .unwrap_or(false)
         );
 
-        Ok(())
+        self.leave_passthrough().await
     }
 
     #[message]
@@ -597,7 +1568,9 @@ impl Player {
                 PlayerUpdate::GuildId(id) => self.guild_id = id,
                 PlayerUpdate::Track(track) => self.track = track,
                 PlayerUpdate::Volume(vol) => self.volume = vol,
-                PlayerUpdate::Position(pos) => self.state.position = pos,
+                PlayerUpdate::Position(pos) => {
+                    self.state.position = self.source_position.to_source_ms(pos as u64) as u32
+                }
                 PlayerUpdate::Connected(connected) => self.state.connected = connected,
                 PlayerUpdate::Paused(paused) => self.paused = paused,
                 PlayerUpdate::Active(active) => self.internal.active = active,
@@ -605,6 +1578,129 @@ impl Player {
         }
     }
 
+    /// Ends a stalled handle right away, the caller reports it as a load failure instead of its End event.
+    /// Returns false if the handle is no longer the current one
+    #[message]
+    pub fn end_stuck_track(&mut self, uuid: Uuid) -> bool {
+        let Some(handle) = self
+            .internal
+            .handle
+            .clone()
+            .filter(|handle| handle.uuid() == uuid)
+        else {
+            return false;
+        };
+
+        self.track_ended(uuid);
+        self.internal.swapped.insert(uuid);
+        handle.stop().ok();
+
+        true
+    }
+
+    /// Returns why the handle ended, clearing the player state if it was the current track.
+    /// None means the handle was swapped for the same track and its end should not be reported
+    #[message]
+    pub fn track_ended(&mut self, uuid: Uuid) -> Option<ApiTrackEndReason> {
+        if self.internal.swapped.remove(&uuid) {
+            return None;
+        }
+
+        let reason = self
+            .internal
+            .end_reasons
+            .remove(&uuid)
+            .unwrap_or(ApiTrackEndReason::Finished);
+
+        if self
+            .internal
+            .handle
+            .as_ref()
+            .is_some_and(|handle| handle.uuid() == uuid)
+        {
+            if let Some(task) = self.internal.end_time_task.take() {
+                task.abort();
+            }
+            self.internal.handle = None;
+            self.internal.active = false;
+            self.internal.last_activity = Instant::now();
+            self.track = None;
+            self.state.position = 0;
+        }
+
+        Some(reason)
+    }
+
+    async fn send_track_end(&self, track: ApiTrack, reason: ApiTrackEndReason) {
+        let event = ApiTrackEnd {
+            guild_id: self.guild_id.0.get(),
+            track,
+            reason,
+        };
+
+        self.send_event(ApiPlayerEvents::TrackEndEvent(event)).await;
+    }
+
+    async fn send_load_failed(&self, track: ApiTrack, error: ResolverError) {
+        tracing::warn!(
+            "Failed to load track [{}] for GuildId [{}]: {}",
+            track.info.identifier,
+            self.guild_id,
+            error
+        );
+
+        let event = ApiTrackException {
+            guild_id: self.guild_id.0.get(),
+            track: track.clone(),
+            exception: ApiException {
+                guild_id: self.guild_id.0.get(),
+                message: Some("Failed to load the track".to_string()),
+                severity: "fault".to_string(),
+                cause: error.to_string(),
+            },
+        };
+
+        self.send_event(ApiPlayerEvents::TrackExceptionEvent(event))
+            .await;
+
+        self.send_track_end(track, ApiTrackEndReason::LoadFailed)
+            .await;
+    }
+
+    async fn send_filter_fallback(&self, track: ApiTrack, reason: String) {
+        tracing::warn!(
+            "Playing track [{}] unfiltered for GuildId [{}]: {}",
+            track.info.identifier,
+            self.guild_id,
+            reason
+        );
+
+        let event = ApiTrackException {
+            guild_id: self.guild_id.0.get(),
+            track,
+            exception: ApiException {
+                guild_id: self.guild_id.0.get(),
+                message: Some(
+                    "Filters could not be applied, playing the track unfiltered".to_string(),
+                ),
+                severity: "common".to_string(),
+                cause: reason,
+            },
+        };
+
+        self.send_event(ApiPlayerEvents::TrackExceptionEvent(event))
+            .await;
+    }
+
+    async fn send_event(&self, event: ApiPlayerEvents) {
+        let Ok(serialized) = serde_json::to_string(&ApiNodeMessage::Event(Box::new(event))) else {
+            return;
+        };
+
+        self.send_to_player_websocket(Message::Text(Utf8Bytes::from(serialized)))
+            .await;
+    }
+
     #[message]
     pub async fn send_to_player_websocket(&self, message: Message) {
         let Some(actor_ref) = self.internal.websocket.upgrade() else {
@@ -33,6 +33,8 @@ pub struct WebSocketClientData {
     pub session_id: String,
     pub resume: bool,
     pub timeout: u32,
+    pub connected: bool,
+    pub queued_messages: usize,
 }
 
 pub struct WebSocketClient {
@@ -53,6 +55,8 @@ impl From<&WebSocketClient> for WebSocketClientData {
             session_id: value.session_id.clone(),
             resume: value.resume.load(Ordering::Acquire),
             timeout: value.timeout.load(Ordering::Acquire),
+            connected: value.sender.is_some(),
+            queued_messages: value.message_queue.len(),
         }
     }
 }